@@ -18,6 +18,12 @@ macro_rules! make_direction_iter {
             }
         }
 
+        impl Default for $iter_name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
         impl Iterator for $iter_name {
             type Item = $type;
 
@@ -60,6 +66,12 @@ macro_rules! make_subdirection_iter {
             }
         }
 
+        impl Default for $iter_name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
         impl Iterator for $iter_name {
             type Item = Direction;
             fn next(&mut self) -> Option<Self::Item> {
@@ -2,6 +2,7 @@ mod bitmap;
 mod cardinal;
 mod direction;
 mod iter;
+mod neighbors;
 mod ordinal;
 mod table;
 
@@ -10,6 +11,7 @@ pub mod prelude {
     pub use crate::cardinal::*;
     pub use crate::direction::*;
     pub use crate::iter::*;
+    pub use crate::neighbors::*;
     pub use crate::ordinal::*;
     pub use crate::table::*;
 
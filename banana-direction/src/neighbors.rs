@@ -0,0 +1,216 @@
+use crate::prelude::*;
+use banana_grid::prelude::{GridPoint, GridRead, IVec2};
+use std::marker::PhantomData;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Which of the eight cells around a point count as its neighbors.
+///
+/// Shared by everything that steps from a cell to the cells around it, like flood fills
+/// and pathfinding.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// North, east, south and west.
+    #[default]
+    Cardinal,
+    /// The four diagonals.
+    Ordinal,
+    /// All eight surrounding cells.
+    All,
+    /// Only the directions in the bitmap.
+    Custom(DirectionBitmap),
+}
+
+impl Neighborhood {
+    pub const fn bitmap(self) -> DirectionBitmap {
+        match self {
+            Neighborhood::Cardinal => DirectionBitmap::all_cardinal(),
+            Neighborhood::Ordinal => DirectionBitmap::all_ordinal(),
+            Neighborhood::All => DirectionBitmap::all(),
+            Neighborhood::Custom(bitmap) => bitmap,
+        }
+    }
+
+    /// Iterate over the directions of the neighbors, clockwise from north.
+    #[inline]
+    pub fn directions(self) -> NeighborhoodIter {
+        NeighborhoodIter { bitmap: self.bitmap(), directions: Direction::all() }
+    }
+}
+
+/// Iterator over the directions of a [`Neighborhood`].
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct NeighborhoodIter {
+    bitmap: DirectionBitmap,
+    directions: DirectionIter,
+}
+
+impl Iterator for NeighborhoodIter {
+    type Item = Direction;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bitmap = self.bitmap;
+        self.directions.by_ref().find(|direction| bitmap.has(*direction))
+    }
+}
+
+impl From<DirectionBitmap> for Neighborhood {
+    fn from(bitmap: DirectionBitmap) -> Self {
+        Neighborhood::Custom(bitmap)
+    }
+}
+
+/// Neighborhood queries for any grid that can be read.
+///
+/// Neighbors are visited clockwise from north, and those outside the grid are skipped.
+/// Grids read with an `EdgeMode` also yield the neighbors past their edge that the mode
+/// gives a value, at the point they resolve to.
+pub trait GridNeighbors<T>: GridRead<T> + Sized {
+    /// The neighbors of `point`, with the direction leading to each.
+    fn neighbors(&self, point: impl GridPoint, kind: Neighborhood) -> Neighbors<'_, T, Self> {
+        Neighbors(self.neighbors_with_values(point, kind))
+    }
+
+    /// The neighbors of `point`, with the direction leading to each and the value there.
+    fn neighbors_with_values(
+        &self,
+        point: impl GridPoint,
+        kind: Neighborhood,
+    ) -> NeighborsWithValues<'_, T, Self> {
+        NeighborsWithValues {
+            grid: self,
+            point: point.as_ivec2(),
+            directions: kind.directions(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, G: GridRead<T>> GridNeighbors<T> for G {}
+
+/// Iterator over the neighbors of a point and their values, from
+/// [`GridNeighbors::neighbors_with_values`].
+#[derive(Debug, Clone)]
+pub struct NeighborsWithValues<'a, T, G> {
+    grid: &'a G,
+    point: IVec2,
+    directions: NeighborhoodIter,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: 'a, G: GridRead<T>> Iterator for NeighborsWithValues<'a, T, G> {
+    type Item = (Direction, IVec2, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for direction in self.directions.by_ref() {
+            let Some(point) = self.grid.resolve(self.point + direction.coord()) else { continue };
+            if let Some(value) = self.grid.get(point) {
+                return Some((direction, point, value));
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the neighbors of a point, from [`GridNeighbors::neighbors`].
+#[derive(Debug, Clone)]
+pub struct Neighbors<'a, T, G>(NeighborsWithValues<'a, T, G>);
+
+impl<'a, T: 'a, G: GridRead<T>> Iterator for Neighbors<'a, T, G> {
+    type Item = (Direction, IVec2);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(direction, point, _)| (direction, point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::new_fn([4, 3], |p| (p.y * 10 + p.x) as u32)
+    }
+
+    #[test]
+    fn middle() {
+        let grid = grid();
+        let all: Vec<_> = grid.neighbors_with_values([1, 1], Neighborhood::All).collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], (Direction::North, IVec2::new(1, 0), &1));
+        assert_eq!(all[3], (Direction::SouthEast, IVec2::new(2, 2), &22));
+
+        let cardinal: Vec<_> =
+            grid.neighbors([1, 1], Neighborhood::Cardinal).map(|(d, _)| d).collect();
+        assert_eq!(cardinal, DirectionsCardinal.into_iter().collect::<Vec<_>>());
+        let ordinal: Vec<_> =
+            grid.neighbors([1, 1], Neighborhood::Ordinal).map(|(d, _)| d).collect();
+        assert_eq!(ordinal, DirectionsOrdinal.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn directions() {
+        let cardinal: Vec<_> = Neighborhood::Cardinal.directions().collect();
+        assert_eq!(cardinal, DirectionsCardinal.into_iter().collect::<Vec<_>>());
+        assert_eq!(Neighborhood::All.directions().count(), 8);
+        let custom = Neighborhood::from(Direction::West.bitmap() | Direction::North.bitmap());
+        assert_eq!(custom.directions().collect::<Vec<_>>(), [Direction::North, Direction::West]);
+    }
+
+    #[test]
+    fn edges() {
+        let grid = grid();
+        let corner: Vec<_> = grid.neighbors_with_values([0, 0], Neighborhood::All).collect();
+        assert_eq!(
+            corner,
+            [
+                (Direction::East, IVec2::new(1, 0), &1),
+                (Direction::SouthEast, IVec2::new(1, 1), &11),
+                (Direction::South, IVec2::new(0, 1), &10),
+            ]
+        );
+        assert_eq!(grid.neighbors([3, 1], Neighborhood::Cardinal).count(), 3);
+        assert_eq!(grid.neighbors([3, 2], Neighborhood::Ordinal).count(), 1);
+        assert_eq!(grid.neighbors([-2, 0], Neighborhood::All).count(), 0);
+    }
+
+    #[test]
+    fn custom() {
+        let grid = grid();
+        let kind = Neighborhood::from(Direction::East.bitmap() | Direction::NorthWest.bitmap());
+        let points: Vec<_> = grid.neighbors([1, 1], kind).map(|(_, p)| p).collect();
+        assert_eq!(points, [IVec2::new(2, 1), IVec2::new(0, 0)]);
+        assert_eq!(grid.neighbors([1, 1], DirectionBitmap::empty().into()).count(), 0);
+
+        // Views and other grids get the same queries.
+        let view = grid.view([1, 1], [3, 2]);
+        let values: Vec<_> =
+            view.neighbors_with_values([0, 0], Neighborhood::All).map(|(.., v)| *v).collect();
+        assert_eq!(values, [12, 22, 21]);
+    }
+
+    #[test]
+    fn edge_modes() {
+        let grid = grid();
+        let wrapped = grid.with_edges(EdgeMode::WrapX);
+        let west: Vec<_> = wrapped.neighbors_with_values([0, 0], Neighborhood::Cardinal).collect();
+        assert_eq!(
+            west,
+            [
+                (Direction::East, IVec2::new(1, 0), &1),
+                (Direction::South, IVec2::new(0, 1), &10),
+                (Direction::West, IVec2::new(3, 0), &3),
+            ]
+        );
+
+        let walled = grid.with_edges(EdgeMode::Constant(99));
+        let north: Vec<_> = walled.neighbors_with_values([1, 0], Neighborhood::All).collect();
+        assert_eq!(north.len(), 8);
+        assert_eq!(north[0], (Direction::North, IVec2::new(1, -1), &99));
+    }
+}
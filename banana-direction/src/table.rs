@@ -1,6 +1,5 @@
 use crate::prelude::*;
 use std::mem;
-use std::mem::MaybeUninit;
 use std::ops::{Index, IndexMut};
 use std::slice;
 
@@ -34,13 +33,9 @@ macro_rules! make_direction_table {
 
         impl<T> $table_type<T> {
             pub fn new_fn<F: FnMut($direction_type) -> T>(mut f: F) -> Self {
-                let values = unsafe {
-                    let mut values: [T; $count] = MaybeUninit::uninit().assume_init();
-                    for i in 0..$count {
-                        values[i] = f(mem::transmute(i as u8));
-                    }
-                    values
-                };
+                let values = std::array::from_fn(|i| {
+                    f(unsafe { mem::transmute::<u8, $direction_type>(i as u8) })
+                });
                 Self { values }
             }
 
@@ -60,11 +55,11 @@ macro_rules! make_direction_table {
                 &mut self.values[direction as usize]
             }
 
-            pub fn iter(&self) -> DirectionTableIter<T> {
+            pub fn iter(&self) -> DirectionTableIter<'_, T> {
                 self.values.iter()
             }
 
-            pub fn iter_mut(&mut self) -> DirectionTableIterMut<T> {
+            pub fn iter_mut(&mut self) -> DirectionTableIterMut<'_, T> {
                 self.values.iter_mut()
             }
 
@@ -72,38 +67,24 @@ macro_rules! make_direction_table {
                 $direction_iter::new()
             }
 
-            pub fn enumerate(&self) -> $enumerate_type<T> {
+            pub fn enumerate(&self) -> $enumerate_type<'_, T> {
                 self.directions().zip(self.iter())
             }
 
-            pub fn enumerate_mut(&mut self) -> $enumerate_mut_type<T> {
+            pub fn enumerate_mut(&mut self) -> $enumerate_mut_type<'_, T> {
                 self.directions().zip(self.iter_mut())
             }
         }
 
         impl<T: Clone> $table_type<T> {
             pub fn new_clone(value: T) -> Self {
-                let values = unsafe {
-                    let mut values: [T; $count] = MaybeUninit::uninit().assume_init();
-                    for i in 0..$count {
-                        values[i] = value.clone();
-                    }
-                    values
-                };
-                Self { values }
+                Self { values: std::array::from_fn(|_| value.clone()) }
             }
         }
 
         impl<T: Default> $table_type<T> {
             pub fn new_default() -> Self {
-                let values = unsafe {
-                    let mut values: [T; $count] = MaybeUninit::uninit().assume_init();
-                    for i in 0..$count {
-                        values[i] = Default::default();
-                    }
-                    values
-                };
-                Self { values }
+                Self { values: std::array::from_fn(|_| Default::default()) }
             }
         }
 
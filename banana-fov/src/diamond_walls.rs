@@ -0,0 +1,64 @@
+use crate::prelude::*;
+use crate::recursive_shadowcast::{cast_octants, TileShape};
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// Shadowcasting where every cell is treated as the diamond inscribed in its square.
+///
+/// Walls cast narrower shadows than with [`RecursiveShadowcast`], so pillars hide less
+/// and it is easier to see past the corners of rooms.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiamondWalls;
+
+impl FovAlgorithm for DiamondWalls {
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        cast_octants(grid, origin.as_ivec2(), radius, distance, is_opaque, TileShape::Diamond)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn fov(algorithm: impl FovAlgorithm, grid: &Grid<bool>, origin: IVec2) -> VisibilityMap {
+        algorithm.compute(grid, origin, 100.0, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new([9, 7], false);
+        assert_eq!(fov(DiamondWalls, &grid, IVec2::new(4, 3)).count(), 63);
+    }
+
+    #[test]
+    fn narrower_shadows() {
+        let grid = Grid::new_fn([21, 21], |p| p.x % 4 == 0 && p.y % 4 == 0);
+        let origin = IVec2::new(10, 11);
+        let diamond = fov(DiamondWalls, &grid, origin);
+        let square = fov(RecursiveShadowcast, &grid, origin);
+
+        assert!(diamond.count() > square.count());
+        assert!(square.iter().all(|p| diamond.is_visible(p)));
+    }
+
+    #[test]
+    fn pillar() {
+        let grid = Grid::new_fn([11, 5], |p| p == IVec2::new(7, 3));
+        let visible = fov(DiamondWalls, &grid, IVec2::new(3, 3));
+        assert!(visible.is_visible([7, 3]));
+        assert!(!visible.is_visible([9, 3]));
+    }
+}
@@ -0,0 +1,89 @@
+use crate::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// A field of view algorithm.
+///
+/// Every implementation runs over any [`GridRead`], such as a grid or a view of one, and
+/// produces a [`VisibilityMap`], so algorithms can be swapped and compared without touching
+/// the calling code.
+pub trait FovAlgorithm {
+    /// Computes the cells visible from `origin` within `radius`, measured with `distance`.
+    ///
+    /// `is_opaque` decides which cells block sight. Cells outside the grid block sight and
+    /// are never visible, unless the grid is read [with edges](GridRead::with_edges): sight
+    /// then goes past the edge, marking the cells that wrap or clamp there as visible. The
+    /// origin is always visible when it lies inside the grid.
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn fov(algorithm: impl FovAlgorithm, grid: &impl GridRead<bool>) -> VisibilityMap {
+        algorithm.compute(grid, IVec2::new(2, 1), 100.0, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    fn unlimited(algorithm: impl FovAlgorithm, grid: &impl GridRead<bool>) -> VisibilityMap {
+        algorithm.compute(grid, IVec2::new(2, 1), f32::INFINITY, DistanceAlg::Pythagoras, |w| *w)
+    }
+
+    fn visible_row(visible: &VisibilityMap) -> String {
+        (0..10).map(|x| if visible.is_visible([x, 1]) { '*' } else { ' ' }).collect()
+    }
+
+    #[test]
+    fn wrapped_corridor() {
+        // A corridor around the world, with one wall across it.
+        let grid = Grid::new_fn([10, 3], |p| p.y != 1 || p.x == 5);
+        let wrapped = grid.with_edges(EdgeMode::WrapX);
+
+        for (bounded, wrapped) in [
+            (fov(SymmetricShadowcast, &grid), fov(SymmetricShadowcast, &wrapped)),
+            (fov(RecursiveShadowcast, &grid), fov(RecursiveShadowcast, &wrapped)),
+            (fov(DiamondWalls, &grid), fov(DiamondWalls, &wrapped)),
+            (fov(PermissiveFov, &grid), fov(PermissiveFov, &wrapped)),
+            (fov(RayCast, &grid), fov(RayCast, &wrapped)),
+        ] {
+            assert_eq!(visible_row(&bounded), "******    ");
+            assert_eq!(visible_row(&wrapped), "**********");
+        }
+    }
+
+    #[test]
+    fn constant_edges() {
+        let grid = Grid::new([5, 3], false);
+        let open = grid.with_edges(EdgeMode::Constant(false));
+        let visible = fov(SymmetricShadowcast, &open);
+        assert_eq!(visible.count(), 15);
+    }
+
+    #[test]
+    fn unlimited_radius() {
+        // Nothing ever blocks sight on an open torus, so only the grid size stops it.
+        let grid = Grid::new([10, 3], false);
+        let wrapped = grid.with_edges(EdgeMode::Wrap);
+        for visible in [
+            unlimited(SymmetricShadowcast, &wrapped),
+            unlimited(RecursiveShadowcast, &wrapped),
+            unlimited(DiamondWalls, &wrapped),
+            unlimited(PermissiveFov, &wrapped),
+            unlimited(RayCast, &wrapped),
+        ] {
+            assert_eq!(visible.count(), 30);
+        }
+    }
+}
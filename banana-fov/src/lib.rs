@@ -0,0 +1,17 @@
+mod diamond_walls;
+mod fov_algorithm;
+mod permissive;
+mod ray_cast;
+mod recursive_shadowcast;
+mod symmetric_shadowcast;
+mod visibility_map;
+
+pub mod prelude {
+    pub use crate::diamond_walls::*;
+    pub use crate::fov_algorithm::*;
+    pub use crate::permissive::*;
+    pub use crate::ray_cast::*;
+    pub use crate::recursive_shadowcast::*;
+    pub use crate::symmetric_shadowcast::*;
+    pub use crate::visibility_map::*;
+}
@@ -0,0 +1,303 @@
+use crate::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// Precise permissive field of view, as described by Jonathon Duerig.
+///
+/// A cell is visible if any unobstructed line can be drawn from any point of the origin
+/// cell to any point of the target cell. This is the most generous of the algorithms, and
+/// it is symmetric.
+///
+/// See: [Precise Permissive Field of View](http://www.roguebasin.com/index.php/Precise_Permissive_Field_of_View)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PermissiveFov;
+
+impl FovAlgorithm for PermissiveFov {
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        let origin = origin.as_ivec2();
+        let mut visible = VisibilityMap::new(grid.size());
+        if !grid.in_bounds(origin) {
+            return visible;
+        }
+        visible.set_visible(origin);
+
+        // No view needs to extend further than the far side of the grid.
+        let reach = grid.width().max(grid.height()) as f32;
+        let extent = radius.max(0.0).min(reach).ceil() as i32;
+        if extent == 0 {
+            return visible;
+        }
+
+        let mut quadrant = Quadrant {
+            origin,
+            extent,
+            views: Vec::new(),
+            bumps: Vec::new(),
+            blocks: |p: IVec2| grid.get(p).is_none_or(&is_opaque),
+            reveal: |p: IVec2| {
+                (distance.distance2d(origin, p) <= radius).then(|| grid.resolve(p).unwrap_or(p))
+            },
+        };
+        for dir in [IVec2::new(1, 1), IVec2::new(-1, 1), IVec2::new(1, -1), IVec2::new(-1, -1)] {
+            quadrant.check(dir, &mut visible);
+        }
+
+        visible
+    }
+}
+
+/// A line between two lattice points, in quadrant-local coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    near: IVec2,
+    far: IVec2,
+}
+
+impl Line {
+    fn new(near: IVec2, far: IVec2) -> Self {
+        Self { near, far }
+    }
+
+    /// Positive when the point is below the line, negative when it is above it.
+    fn relative_slope(&self, p: IVec2) -> i64 {
+        let (xi, yi) = widen(self.near);
+        let (xf, yf) = widen(self.far);
+        let (x, y) = widen(p);
+        (yf - yi) * (xf - x) - (xf - xi) * (yf - y)
+    }
+
+    fn is_below(&self, p: IVec2) -> bool {
+        self.relative_slope(p) > 0
+    }
+
+    fn is_below_or_collinear(&self, p: IVec2) -> bool {
+        self.relative_slope(p) >= 0
+    }
+
+    fn is_above(&self, p: IVec2) -> bool {
+        self.relative_slope(p) < 0
+    }
+
+    fn is_above_or_collinear(&self, p: IVec2) -> bool {
+        self.relative_slope(p) <= 0
+    }
+
+    fn is_collinear(&self, p: IVec2) -> bool {
+        self.relative_slope(p) == 0
+    }
+
+    fn is_line_collinear(&self, other: &Line) -> bool {
+        self.is_collinear(other.near) && self.is_collinear(other.far)
+    }
+}
+
+/// Widens a point so slope products can't overflow on large grids.
+fn widen(p: IVec2) -> (i64, i64) {
+    (i64::from(p.x), i64::from(p.y))
+}
+
+/// A wall corner that a view's line has been bent around.
+#[derive(Debug, Clone, Copy)]
+struct Bump {
+    point: IVec2,
+    parent: Option<usize>,
+}
+
+/// A wedge of light bounded by a shallow and a steep line.
+#[derive(Debug, Clone, Copy)]
+struct View {
+    shallow: Line,
+    steep: Line,
+    shallow_bump: Option<usize>,
+    steep_bump: Option<usize>,
+}
+
+/// Scans one quadrant around the origin at a time.
+struct Quadrant<B, R> {
+    origin: IVec2,
+    extent: i32,
+    views: Vec<View>,
+    /// Arena holding every view's bumps, linked through `Bump::parent`.
+    bumps: Vec<Bump>,
+    blocks: B,
+    /// The cell to mark visible when a point is seen, if it's within range.
+    reveal: R,
+}
+
+impl<B, R> Quadrant<B, R>
+where
+    B: Fn(IVec2) -> bool,
+    R: Fn(IVec2) -> Option<IVec2>,
+{
+    fn check(&mut self, dir: IVec2, visible: &mut VisibilityMap) {
+        let extent = self.extent;
+        self.bumps.clear();
+        self.views.clear();
+        self.views.push(View {
+            shallow: Line::new(IVec2::new(0, 1), IVec2::new(extent, 0)),
+            steep: Line::new(IVec2::new(1, 0), IVec2::new(0, extent)),
+            shallow_bump: None,
+            steep_bump: None,
+        });
+
+        // Walk the quadrant in diagonals of increasing distance from the origin.
+        for i in 1..=2 * extent {
+            for j in (i - extent).max(0)..=i.min(extent) {
+                if self.views.is_empty() {
+                    return;
+                }
+                self.visit(IVec2::new(i - j, j), dir, visible);
+            }
+        }
+    }
+
+    fn visit(&mut self, local: IVec2, dir: IVec2, visible: &mut VisibilityMap) {
+        let top_left = local + IVec2::Y;
+        let bottom_right = local + IVec2::X;
+
+        // Skip the views that lie entirely below the cell.
+        let Some(idx) =
+            self.views.iter().position(|view| !view.steep.is_below_or_collinear(bottom_right))
+        else {
+            return;
+        };
+        if self.views[idx].shallow.is_above_or_collinear(top_left) {
+            return;
+        }
+
+        let point = self.origin + local * dir;
+        if let Some(cell) = (self.reveal)(point) {
+            visible.set_visible(cell);
+        }
+        if !(self.blocks)(point) {
+            return;
+        }
+
+        let view = self.views[idx];
+        let above_shallow = view.shallow.is_above(bottom_right);
+        let below_steep = view.steep.is_below(top_left);
+        if above_shallow && below_steep {
+            // The wall fills the view completely.
+            self.views.remove(idx);
+        } else if above_shallow {
+            self.add_shallow_bump(top_left, idx);
+            self.check_view(idx);
+        } else if below_steep {
+            self.add_steep_bump(bottom_right, idx);
+            self.check_view(idx);
+        } else {
+            // The wall sits in the middle of the view, splitting it in two.
+            self.views.insert(idx, view);
+            self.add_steep_bump(bottom_right, idx);
+            let steeper = if self.check_view(idx) { idx + 1 } else { idx };
+            self.add_shallow_bump(top_left, steeper);
+            self.check_view(steeper);
+        }
+    }
+
+    fn add_shallow_bump(&mut self, point: IVec2, idx: usize) {
+        let view = &mut self.views[idx];
+        view.shallow.far = point;
+        self.bumps.push(Bump { point, parent: view.shallow_bump });
+        view.shallow_bump = Some(self.bumps.len() - 1);
+
+        let mut current = view.steep_bump;
+        while let Some(bump) = current.map(|i| self.bumps[i]) {
+            if view.shallow.is_above(bump.point) {
+                view.shallow.near = bump.point;
+            }
+            current = bump.parent;
+        }
+    }
+
+    fn add_steep_bump(&mut self, point: IVec2, idx: usize) {
+        let view = &mut self.views[idx];
+        view.steep.far = point;
+        self.bumps.push(Bump { point, parent: view.steep_bump });
+        view.steep_bump = Some(self.bumps.len() - 1);
+
+        let mut current = view.shallow_bump;
+        while let Some(bump) = current.map(|i| self.bumps[i]) {
+            if view.steep.is_below(bump.point) {
+                view.steep.near = bump.point;
+            }
+            current = bump.parent;
+        }
+    }
+
+    /// Removes a view that has narrowed down to a single line through the origin cell's
+    /// corners. Returns whether the view is still alive.
+    fn check_view(&mut self, idx: usize) -> bool {
+        let View { shallow, steep, .. } = self.views[idx];
+        if shallow.is_line_collinear(&steep)
+            && (shallow.is_collinear(IVec2::Y) || shallow.is_collinear(IVec2::X))
+        {
+            self.views.remove(idx);
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn fov(algorithm: impl FovAlgorithm, grid: &Grid<bool>, origin: IVec2) -> VisibilityMap {
+        algorithm.compute(grid, origin, 100.0, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new([9, 7], false);
+        assert_eq!(fov(PermissiveFov, &grid, IVec2::new(4, 3)).count(), 63);
+        assert_eq!(fov(PermissiveFov, &grid, IVec2::new(0, 6)).count(), 63);
+    }
+
+    #[test]
+    fn pillar() {
+        let grid = Grid::new_fn([11, 5], |p| p == IVec2::new(7, 3));
+        let visible = fov(PermissiveFov, &grid, IVec2::new(3, 3));
+        assert!(visible.is_visible([7, 3]));
+        assert!(!visible.is_visible([8, 3]));
+        assert!(!visible.is_visible([10, 3]));
+        assert!(visible.is_visible([10, 2]));
+    }
+
+    #[test]
+    fn sees_more_than_shadowcasting() {
+        let grid = Grid::new_fn([24, 24], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let origin = IVec2::new(11, 12);
+        let permissive = fov(PermissiveFov, &grid, origin);
+        let shadowcast = fov(RecursiveShadowcast, &grid, origin);
+
+        assert!(permissive.count() > shadowcast.count());
+    }
+
+    #[test]
+    fn symmetry() {
+        let grid = Grid::new_fn([12, 12], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let floors: Vec<IVec2> = grid.size().iter().filter(|p| !grid[*p]).collect();
+        let maps: Vec<VisibilityMap> =
+            floors.iter().map(|p| fov(PermissiveFov, &grid, *p)).collect();
+
+        for (a, map_a) in floors.iter().zip(&maps) {
+            for (b, map_b) in floors.iter().zip(&maps) {
+                assert_eq!(map_a.is_visible(*b), map_b.is_visible(*a), "{a} <-> {b}");
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+use banana_geometry::prelude::{DistanceAlg, GridLineIter};
+use banana_grid::prelude::*;
+
+/// Casts a Bresenham ray from the origin to every cell on the edge of the radius.
+///
+/// Each ray lights cells until it hits a wall, which is lit as well. Simple and
+/// predictable, but it leaves gaps at long range and is not symmetric.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RayCast;
+
+impl FovAlgorithm for RayCast {
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        let origin = origin.as_ivec2();
+        let mut visible = VisibilityMap::new(grid.size());
+        if !grid.in_bounds(origin) {
+            return visible;
+        }
+        visible.set_visible(origin);
+
+        // No ray needs to reach further than the far side of the grid.
+        let reach = grid.width().max(grid.height()) as f32;
+        let r = radius.max(0.0).min(reach).ceil() as i32;
+
+        let perimeter = (-r..=r).flat_map(|i| {
+            [IVec2::new(i, -r), IVec2::new(i, r), IVec2::new(-r, i), IVec2::new(r, i)]
+        });
+        for target in perimeter {
+            for point in GridLineIter::new(origin, origin + target).skip(1) {
+                if distance.distance2d(origin, point) > radius {
+                    break;
+                }
+                let Some(cell) = grid.get(point) else { break };
+
+                visible.set_visible(grid.resolve(point).unwrap_or(point));
+                if is_opaque(cell) {
+                    break;
+                }
+            }
+        }
+
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn fov(grid: &Grid<bool>, origin: IVec2, radius: f32) -> VisibilityMap {
+        RayCast.compute(grid, origin, radius, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new([9, 7], false);
+        assert_eq!(fov(&grid, IVec2::new(4, 3), 100.0).count(), 63);
+        assert_eq!(fov(&grid, IVec2::new(8, 6), 100.0).count(), 63);
+    }
+
+    #[test]
+    fn pillar() {
+        let grid = Grid::new_fn([11, 5], |p| p == IVec2::new(7, 3));
+        let visible = fov(&grid, IVec2::new(3, 3), 100.0);
+        assert!(visible.is_visible([7, 3]));
+        assert!(!visible.is_visible([8, 3]));
+        assert!(!visible.is_visible([10, 3]));
+    }
+
+    #[test]
+    fn radius() {
+        let grid = Grid::new([21, 21], false);
+        let visible = fov(&grid, IVec2::new(10, 10), 4.0);
+        assert!(visible.is_visible([14, 10]));
+        assert!(!visible.is_visible([15, 10]));
+        assert!(!visible.is_visible([13, 13]));
+    }
+}
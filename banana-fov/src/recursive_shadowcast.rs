@@ -0,0 +1,171 @@
+use crate::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// Classic recursive shadowcasting, as described by Björn Bergström.
+///
+/// Walls are full squares and a cell is visible when any part of it is lit from the
+/// center of the origin. Fast and well understood, but not symmetric.
+///
+/// See: [FOV using recursive shadowcasting](http://www.roguebasin.com/index.php/FOV_using_recursive_shadowcasting)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RecursiveShadowcast;
+
+impl FovAlgorithm for RecursiveShadowcast {
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        cast_octants(grid, origin.as_ivec2(), radius, distance, is_opaque, TileShape::Square)
+    }
+}
+
+/// The shape a cell presents to light, both when being lit and when casting shadows.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TileShape {
+    Square,
+    Diamond,
+}
+
+impl TileShape {
+    /// The range of slopes (column / depth) covered by the cell at (depth, col) in an
+    /// octant.
+    fn span(self, depth: i32, col: i32) -> (f32, f32) {
+        let depth = depth as f32;
+        let col = col as f32;
+        match self {
+            TileShape::Square => ((col - 0.5) / (depth + 0.5), (col + 0.5) / (depth - 0.5)),
+            TileShape::Diamond => {
+                ((col - 0.5) / depth, ((col + 0.5) / depth).max(col / (depth - 0.5)))
+            }
+        }
+    }
+}
+
+/// The (depth, column) axes of the eight octants around the origin.
+const OCTANTS: [(IVec2, IVec2); 8] = [
+    (IVec2::X, IVec2::Y),
+    (IVec2::X, IVec2::NEG_Y),
+    (IVec2::NEG_X, IVec2::Y),
+    (IVec2::NEG_X, IVec2::NEG_Y),
+    (IVec2::Y, IVec2::X),
+    (IVec2::Y, IVec2::NEG_X),
+    (IVec2::NEG_Y, IVec2::X),
+    (IVec2::NEG_Y, IVec2::NEG_X),
+];
+
+/// Shadowcasts each octant around `origin`, with cells shaped as `shape`.
+pub(crate) fn cast_octants<T, G, F>(
+    grid: &G,
+    origin: IVec2,
+    radius: f32,
+    distance: DistanceAlg,
+    is_opaque: F,
+    shape: TileShape,
+) -> VisibilityMap
+where
+    G: GridRead<T>,
+    F: Fn(&T) -> bool,
+{
+    let mut visible = VisibilityMap::new(grid.size());
+    if !grid.in_bounds(origin) {
+        return visible;
+    }
+    visible.set_visible(origin);
+
+    // No row needs to lie further than the far side of the grid.
+    let reach = grid.width().max(grid.height()) as f32;
+    let max_depth = radius.max(0.0).min(reach).ceil() as i32;
+    let blocks = |p: IVec2| grid.get(p).is_none_or(&is_opaque);
+    let in_range = |p: IVec2| distance.distance2d(origin, p) <= radius;
+
+    for (depth_dir, col_dir) in OCTANTS {
+        // Each entry is a row still to scan: (depth, start slope, end slope).
+        let mut rows = vec![(1, 0.0, 1.0)];
+        while let Some((depth, mut start, end)) = rows.pop() {
+            if depth > max_depth || start >= end {
+                continue;
+            }
+
+            let mut blocked = false;
+            let mut next_start = start;
+            for col in 0..=depth {
+                let (low, high) = shape.span(depth, col);
+                if high <= start {
+                    continue;
+                }
+                if low >= end {
+                    break;
+                }
+
+                let point = origin + depth_dir * depth + col_dir * col;
+                if in_range(point) {
+                    visible.set_visible(grid.resolve(point).unwrap_or(point));
+                }
+
+                let wall = blocks(point);
+                if blocked {
+                    if wall {
+                        next_start = high;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if wall {
+                    blocked = true;
+                    rows.push((depth + 1, start, low));
+                    next_start = high;
+                }
+            }
+
+            if !blocked {
+                rows.push((depth + 1, start, end));
+            }
+        }
+    }
+
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn fov(grid: &Grid<bool>, origin: IVec2) -> VisibilityMap {
+        RecursiveShadowcast.compute(grid, origin, 100.0, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new([9, 7], false);
+        assert_eq!(fov(&grid, IVec2::new(4, 3)).count(), 63);
+        assert_eq!(fov(&grid, IVec2::new(0, 0)).count(), 63);
+    }
+
+    #[test]
+    fn pillar() {
+        let grid = Grid::new_fn([11, 5], |p| p == IVec2::new(7, 3));
+        let visible = fov(&grid, IVec2::new(3, 3));
+        assert!(visible.is_visible([7, 3]));
+        assert!(!visible.is_visible([9, 3]));
+        assert!(visible.is_visible([10, 1]));
+    }
+
+    #[test]
+    fn radius() {
+        let grid = Grid::new([21, 21], false);
+        let visible =
+            RecursiveShadowcast.compute(&grid, [10, 10], 3.0, DistanceAlg::Chebyshev, |w| *w);
+        assert_eq!(visible.count(), 49);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// Symmetric shadowcasting, as described by Albert Ford.
+///
+/// Every floor tile that can see another floor tile is also seen by it, walls are lit
+/// without artifacts, and the origin is always visible. Cells outside the grid block
+/// sight and are never marked visible.
+///
+/// See: [Symmetric Shadowcasting](https://www.albertford.com/shadowcasting/)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SymmetricShadowcast;
+
+impl FovAlgorithm for SymmetricShadowcast {
+    fn compute<T, G, F>(
+        &self,
+        grid: &G,
+        origin: impl GridPoint,
+        radius: f32,
+        distance: DistanceAlg,
+        is_opaque: F,
+    ) -> VisibilityMap
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        let origin = origin.as_ivec2();
+        let mut visible = VisibilityMap::new(grid.size());
+        if !grid.in_bounds(origin) {
+            return visible;
+        }
+        visible.set_visible(origin);
+
+        // No row needs to lie further than the far side of the grid.
+        let reach = grid.width().max(grid.height()) as f32;
+        let max_depth = radius.max(0.0).min(reach).ceil() as i32;
+        let blocks = |p: IVec2| grid.get(p).is_none_or(&is_opaque);
+        let in_range = |p: IVec2| distance.distance2d(origin, p) <= radius;
+
+        for quadrant in Quadrant::ALL {
+            let mut rows = vec![Row::new(1, Slope::new(-1, 1), Slope::new(1, 1))];
+            while let Some(mut row) = rows.pop() {
+                if row.depth > max_depth {
+                    continue;
+                }
+
+                let mut prev_wall = None;
+                for col in row.min_col()..=row.max_col() {
+                    let point = quadrant.transform(origin, row.depth, col);
+                    let wall = blocks(point);
+
+                    if (wall || row.is_symmetric(col)) && in_range(point) {
+                        visible.set_visible(grid.resolve(point).unwrap_or(point));
+                    }
+                    if prev_wall == Some(true) && !wall {
+                        row.start = Slope::of(row.depth, col);
+                    }
+                    if prev_wall == Some(false) && wall {
+                        let mut next = row.next();
+                        next.end = Slope::of(row.depth, col);
+                        rows.push(next);
+                    }
+                    prev_wall = Some(wall);
+                }
+
+                if prev_wall == Some(false) {
+                    rows.push(row.next());
+                }
+            }
+        }
+
+        visible
+    }
+}
+
+/// One of the four 90 degree sectors scanned around the origin.
+#[derive(Debug, Clone, Copy)]
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Quadrant {
+    const ALL: [Quadrant; 4] = [Quadrant::North, Quadrant::East, Quadrant::South, Quadrant::West];
+
+    /// Converts a (depth, column) pair relative to this quadrant into a grid point.
+    fn transform(self, origin: IVec2, depth: i32, col: i32) -> IVec2 {
+        match self {
+            Quadrant::North => IVec2::new(origin.x + col, origin.y - depth),
+            Quadrant::South => IVec2::new(origin.x + col, origin.y + depth),
+            Quadrant::East => IVec2::new(origin.x + depth, origin.y + col),
+            Quadrant::West => IVec2::new(origin.x - depth, origin.y + col),
+        }
+    }
+}
+
+/// An exact rational slope, kept as a fraction to avoid floating point artifacts.
+#[derive(Debug, Clone, Copy)]
+struct Slope {
+    num: i32,
+    den: i32,
+}
+
+impl Slope {
+    fn new(num: i32, den: i32) -> Self {
+        Self { num, den }
+    }
+
+    /// The slope from the origin to the edge of the tile at (depth, col) closest to the
+    /// start of the row.
+    fn of(depth: i32, col: i32) -> Self {
+        Self::new(2 * col - 1, 2 * depth)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    depth: i32,
+    start: Slope,
+    end: Slope,
+}
+
+impl Row {
+    fn new(depth: i32, start: Slope, end: Slope) -> Self {
+        Self { depth, start, end }
+    }
+
+    fn next(&self) -> Self {
+        Self::new(self.depth + 1, self.start, self.end)
+    }
+
+    /// `depth * start`, rounded with ties going up.
+    fn min_col(&self) -> i32 {
+        let Slope { num, den } = self.start;
+        (2 * self.depth * num + den).div_euclid(2 * den)
+    }
+
+    /// `depth * end`, rounded with ties going down.
+    fn max_col(&self) -> i32 {
+        let Slope { num, den } = self.end;
+        -(den - 2 * self.depth * num).div_euclid(2 * den)
+    }
+
+    /// Whether a floor tile's center lies within the row's slopes, which is what keeps
+    /// the algorithm symmetric.
+    fn is_symmetric(&self, col: i32) -> bool {
+        col * self.start.den >= self.depth * self.start.num
+            && col * self.end.den <= self.depth * self.end.num
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+    use banana_utils::Canvas;
+
+    /// Builds a grid from rows of `#` (wall) and `.` (floor).
+    fn parse(rows: &[&str]) -> Grid<bool> {
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| rows[p.y as usize].as_bytes()[p.x as usize] == b'#')
+    }
+
+    fn fov(grid: &Grid<bool>, origin: IVec2, radius: f32) -> VisibilityMap {
+        SymmetricShadowcast.compute(grid, origin, radius, DistanceAlg::Pythagoras, |wall| *wall)
+    }
+
+    fn print(grid: &Grid<bool>, visible: &VisibilityMap) {
+        let mut canvas = Canvas::new(grid.size());
+        for p in grid.size().iter() {
+            let glyph = match (visible.is_visible(p), grid[p]) {
+                (true, true) => '#',
+                (true, false) => '*',
+                (false, _) => ' ',
+            };
+            canvas.put(p, glyph);
+        }
+        canvas.print();
+    }
+
+    #[test]
+    fn open_room() {
+        let grid = Grid::new([11, 11], false);
+        let visible = fov(&grid, IVec2::new(5, 5), 20.0);
+        assert_eq!(visible.count(), 121);
+    }
+
+    #[test]
+    fn radius() {
+        let grid = Grid::new([21, 21], false);
+        let origin = IVec2::new(10, 10);
+
+        let visible = fov(&grid, origin, 5.0);
+        assert!(visible.is_visible([15, 10]));
+        assert!(visible.is_visible([13, 14]));
+        assert!(!visible.is_visible([16, 10]));
+        assert!(!visible.is_visible([14, 14]));
+
+        let visible =
+            SymmetricShadowcast.compute(&grid, origin, 5.0, DistanceAlg::Manhattan, |w| *w);
+        assert!(visible.is_visible([15, 10]));
+        assert!(!visible.is_visible([13, 13]));
+    }
+
+    #[test]
+    fn pillar() {
+        let grid = parse(&[
+            "...........", //
+            "...........",
+            "...........",
+            ".......#...",
+            "...........",
+        ]);
+        let visible = fov(&grid, IVec2::new(3, 3), 20.0);
+        print(&grid, &visible);
+
+        assert!(visible.is_visible([7, 3]));
+        assert!(!visible.is_visible([8, 3]));
+        assert!(!visible.is_visible([10, 3]));
+        assert!(visible.is_visible([10, 1]));
+        assert!(visible.is_visible([10, 4]));
+    }
+
+    #[test]
+    fn corridor() {
+        let grid = parse(&[
+            "###########", //
+            "#.........#",
+            "#####.#####",
+            "#####.#####",
+            "#####.#####",
+        ]);
+        let visible = fov(&grid, IVec2::new(1, 1), 20.0);
+        print(&grid, &visible);
+
+        // The whole corridor and its walls are lit.
+        for x in 0..11 {
+            assert!(visible.is_visible([x, 0]));
+            assert!(visible.is_visible([x, 1]));
+        }
+        // But the side passage is around a corner.
+        assert!(!visible.is_visible([5, 4]));
+        assert!(!visible.is_visible([0, 3]));
+    }
+
+    #[test]
+    fn map_edges() {
+        let grid = Grid::new([8, 6], false);
+        for origin in [[0, 0], [7, 0], [0, 5], [7, 5]] {
+            let visible = fov(&grid, IVec2::from(origin), 100.0);
+            assert_eq!(visible.count(), 48);
+        }
+
+        let visible = fov(&grid, IVec2::new(-1, 3), 100.0);
+        assert_eq!(visible.count(), 0);
+    }
+
+    #[test]
+    fn symmetry() {
+        // A deterministic scattering of walls.
+        let grid = Grid::new_fn([16, 16], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let floors: Vec<IVec2> = grid.size().iter().filter(|p| !grid[*p]).collect();
+        let maps: Vec<VisibilityMap> = floors.iter().map(|p| fov(&grid, *p, 100.0)).collect();
+
+        for (a, map_a) in floors.iter().zip(&maps) {
+            for (b, map_b) in floors.iter().zip(&maps) {
+                assert_eq!(map_a.is_visible(*b), map_b.is_visible(*a), "{a} <-> {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn view() {
+        let grid = Grid::new_fn([16, 16], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let view = grid.view([3, 4], [8, 6]);
+        let cropped = grid.crop([3, 4], [8, 6]);
+        for origin in [[0, 0], [4, 2], [7, 5]] {
+            let from_view = SymmetricShadowcast.compute(
+                &view,
+                origin,
+                100.0,
+                DistanceAlg::Pythagoras,
+                |wall| *wall,
+            );
+            assert_eq!(from_view, fov(&cropped, origin.into(), 100.0));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use banana_grid::prelude::*;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A grid-sized bitset recording which cells are visible.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct VisibilityMap {
+    size: UVec2,
+    bits: Vec<u64>,
+}
+
+impl VisibilityMap {
+    /// Create a new map of the given size where nothing is visible.
+    pub fn new(size: impl Size2d) -> Self {
+        Self { size: size.as_uvec2(), bits: vec![0; size.count().div_ceil(BITS)] }
+    }
+
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.size.width()
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.size.height()
+    }
+
+    /// Marks a point as visible. Points outside the map are ignored.
+    #[inline]
+    pub fn set_visible(&mut self, point: impl GridPoint) {
+        if point.is_valid(self.size) {
+            let idx = point.as_index(self.size.width() as usize);
+            self.bits[idx / BITS] |= 1 << (idx % BITS);
+        }
+    }
+
+    /// Marks a point as not visible. Points outside the map are ignored.
+    #[inline]
+    pub fn set_hidden(&mut self, point: impl GridPoint) {
+        if point.is_valid(self.size) {
+            let idx = point.as_index(self.size.width() as usize);
+            self.bits[idx / BITS] &= !(1 << (idx % BITS));
+        }
+    }
+
+    /// Tests whether a point is visible. Points outside the map are never visible.
+    #[inline]
+    pub fn is_visible(&self, point: impl GridPoint) -> bool {
+        if !point.is_valid(self.size) {
+            return false;
+        }
+        let idx = point.as_index(self.size.width() as usize);
+        self.bits[idx / BITS] & (1 << (idx % BITS)) != 0
+    }
+
+    /// Hides every point.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// The number of visible points.
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// An iterator over every visible point, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.size.iter().filter(|p| self.is_visible(*p))
+    }
+
+    /// Expands the bitset into a `Grid<bool>`.
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::new_fn(self.size, |p| self.is_visible(p))
+    }
+}
@@ -3,6 +3,8 @@ use std::ops::Sub;
 
 /// Enumeration of available 2D Distance algorithms
 #[allow(clippy::module_name_repetitions)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum DistanceAlg {
     /// Use the Pythagoras algorithm for determining distance - sqrt(A^2 + B^2)
     Pythagoras,
@@ -18,8 +20,6 @@ pub enum DistanceAlg {
 }
 
 impl DistanceAlg {
-    /// Provides a 2D distance between points, using the specified algorithm.
-
     /// Provides a 2D distance between points, using the specified algorithm.
     pub fn distance2d<P>(self, start: P, end: P) -> f32
     where
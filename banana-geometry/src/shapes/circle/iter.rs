@@ -0,0 +1,116 @@
+use banana_grid::prelude::IVec2;
+
+/// Walks the first octant of a circle (from `(r, 0)` until `y > x`) with the midpoint
+/// algorithm.
+///
+/// The midpoint test is done in floating point so fractional radii are supported; for
+/// integer radii this yields exactly the cells of the classic integer midpoint algorithm.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn midpoint_octant(radius: f32) -> Vec<IVec2> {
+    let radius = radius.max(0.0);
+    let r2 = radius * radius;
+    let mut x = (radius + 0.5).floor() as i32;
+    let mut y = 0;
+
+    let mut points = Vec::new();
+    while y <= x {
+        points.push(IVec2::new(x, y));
+
+        // Is the midpoint between the two candidate cells of the next row inside the circle?
+        let mid_x = x as f32 - 0.5;
+        let next_y = (y + 1) as f32;
+        if mid_x * mid_x + next_y * next_y - r2 >= 0.0 {
+            x -= 1;
+        }
+        y += 1;
+    }
+    points
+}
+
+/// Iterates over the cells on the outline of a circle.
+///
+/// Every cell is yielded exactly once.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GridCircleOutlineIter {
+    points: std::vec::IntoIter<IVec2>,
+}
+
+impl GridCircleOutlineIter {
+    #[must_use]
+    pub fn new(center: IVec2, radius: f32) -> Self {
+        let mut points = Vec::new();
+        for p in midpoint_octant(radius) {
+            for [x, y] in [[p.x, p.y], [p.y, p.x]] {
+                points.extend([
+                    IVec2::new(x, y),
+                    IVec2::new(-x, y),
+                    IVec2::new(x, -y),
+                    IVec2::new(-x, -y),
+                ]);
+            }
+        }
+        points.sort_unstable_by_key(|p| (p.y, p.x));
+        points.dedup();
+
+        Self { points: points.into_iter().map(|p| center + p).collect::<Vec<_>>().into_iter() }
+    }
+}
+
+impl Iterator for GridCircleOutlineIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.points.next()
+    }
+}
+
+/// Iterates over every cell inside a circle, including its outline, row by row.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GridCircleIter {
+    center: IVec2,
+    /// The half-width of each row, indexed by the row's distance from the center.
+    spans: Vec<i32>,
+    curr: IVec2,
+}
+
+impl GridCircleIter {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn new(center: IVec2, radius: f32) -> Self {
+        let octant = midpoint_octant(radius);
+        let extent = octant.first().map_or(0, |p| p.x);
+
+        let mut spans = vec![0; extent as usize + 1];
+        for p in octant {
+            spans[p.y as usize] = spans[p.y as usize].max(p.x);
+            spans[p.x as usize] = spans[p.x as usize].max(p.y);
+        }
+
+        let top = -(spans.len() as i32 - 1);
+        Self { center, curr: IVec2::new(-spans[top.unsigned_abs() as usize], top), spans }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn span(&self, y: i32) -> Option<i32> {
+        self.spans.get(y.unsigned_abs() as usize).copied()
+    }
+}
+
+impl Iterator for GridCircleIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = self.span(self.curr.y)?;
+        let p = self.curr;
+
+        if self.curr.x < span {
+            self.curr.x += 1;
+        } else {
+            self.curr.y += 1;
+            self.curr.x = -self.span(self.curr.y).unwrap_or(0);
+        }
+        Some(self.center + p)
+    }
+}
@@ -0,0 +1,158 @@
+use super::{GridShape, GridShapeIterator};
+use banana_grid::prelude::{GridPoint, IVec2};
+
+mod iter;
+
+pub use iter::*;
+
+/// A filled circle around a center point.
+///
+/// The radius may be fractional: a radius of `1.5` gives a plumper shape than `1.0`,
+/// without growing all the way to `2.0`.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct GridCircle {
+    /// The center of the circle.
+    pub center: IVec2,
+    /// The radius of the circle, in cells.
+    pub radius: f32,
+}
+
+impl GridCircle {
+    /// Create a new filled circle from its center and radius.
+    #[inline]
+    pub fn new(center: impl GridPoint, radius: f32) -> Self {
+        Self { center: center.as_ivec2(), radius }
+    }
+}
+
+impl GridShape for GridCircle {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::Circle(GridCircleIter::new(self.center, self.radius))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.center
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.center = pos;
+    }
+}
+
+/// The outline of a circle around a center point, rasterized with the midpoint algorithm.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct GridCircleOutline {
+    /// The center of the circle.
+    pub center: IVec2,
+    /// The radius of the circle, in cells.
+    pub radius: f32,
+}
+
+impl GridCircleOutline {
+    /// Create a new circle outline from its center and radius.
+    #[inline]
+    pub fn new(center: impl GridPoint, radius: f32) -> Self {
+        Self { center: center.as_ivec2(), radius }
+    }
+}
+
+impl GridShape for GridCircleOutline {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::CircleOutline(GridCircleOutlineIter::new(self.center, self.radius))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.center
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.center = pos;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::IVec2;
+    use banana_utils::Canvas;
+    use std::collections::HashSet;
+
+    /// Mirrors first-octant offsets into the full set of circle offsets.
+    fn mirror(octant: &[[i32; 2]]) -> HashSet<IVec2> {
+        let mut set = HashSet::new();
+        for &[x, y] in octant {
+            for [x, y] in [[x, y], [y, x]] {
+                set.extend([[x, y], [-x, y], [x, -y], [-x, -y]].map(IVec2::from));
+            }
+        }
+        set
+    }
+
+    #[test]
+    fn outline_matches_midpoint() {
+        let outline: HashSet<IVec2> = GridCircleOutline::new([0, 0], 3.0).iter().collect();
+        assert_eq!(outline, mirror(&[[3, 0], [3, 1], [2, 2]]));
+        assert_eq!(outline.len(), 16);
+
+        let outline: HashSet<IVec2> = GridCircleOutline::new([0, 0], 5.0).iter().collect();
+        assert_eq!(outline, mirror(&[[5, 0], [5, 1], [5, 2], [4, 3]]));
+        assert_eq!(outline.len(), 28);
+    }
+
+    #[test]
+    fn outline_no_duplicates() {
+        for radius in [0.0, 1.0, 2.5, 4.0, 7.3] {
+            let points: Vec<IVec2> = GridCircleOutline::new([0, 0], radius).iter().collect();
+            let set: HashSet<IVec2> = points.iter().copied().collect();
+            assert_eq!(points.len(), set.len());
+        }
+    }
+
+    #[test]
+    fn circle_contains_outline() {
+        for radius in [0.0, 1.0, 1.5, 3.0, 4.5, 6.0] {
+            let filled: HashSet<IVec2> = GridCircle::new([10, 10], radius).iter().collect();
+            let outline: HashSet<IVec2> = GridCircleOutline::new([10, 10], radius).iter().collect();
+            assert!(outline.is_subset(&filled));
+        }
+    }
+
+    #[test]
+    fn circle_radius_one() {
+        let filled: HashSet<IVec2> = GridCircle::new([0, 0], 1.0).iter().collect();
+        let expected: HashSet<IVec2> =
+            [[0, -1], [-1, 0], [0, 0], [1, 0], [0, 1]].map(IVec2::from).into_iter().collect();
+        assert_eq!(filled, expected);
+    }
+
+    #[test]
+    fn circle_fractional_radius() {
+        let small = GridCircle::new([0, 0], 1.0).iter().count();
+        let medium = GridCircle::new([0, 0], 1.5).iter().count();
+        let large = GridCircle::new([0, 0], 2.0).iter().count();
+        assert!(small < medium && medium < large);
+    }
+
+    #[test]
+    fn circle_set_pos() {
+        let mut circle = GridCircle::new([5, 5], 4.0);
+        let before: Vec<IVec2> = circle.iter().collect();
+        circle.set_pos(IVec2::new(8, 3));
+        let after: Vec<IVec2> = circle.iter().map(|p| p - IVec2::new(3, -2)).collect();
+        assert_eq!(before, after);
+
+        let mut canvas = Canvas::new([10, 10]);
+        for p in before {
+            canvas.put(p, '*');
+        }
+        canvas.print();
+    }
+}
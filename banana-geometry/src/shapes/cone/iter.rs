@@ -0,0 +1,52 @@
+use crate::shapes::GridCircleIter;
+use banana_grid::prelude::{GridPoint, IVec2};
+use std::f32::consts::{PI, TAU};
+
+/// Iterates over every cell covered by a cone.
+///
+/// Cells are taken from the filled circle of the cone's range and kept when the angle
+/// from the origin to their center lies within the cone's angular width. The origin is
+/// always yielded.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GridConeIter {
+    circle: GridCircleIter,
+    origin: IVec2,
+    angle: f32,
+    half_width: f32,
+}
+
+impl GridConeIter {
+    pub fn new(origin: impl GridPoint, angle: f32, width: f32, range: f32) -> Self {
+        let origin = origin.as_ivec2();
+        Self {
+            circle: GridCircleIter::new(origin, range),
+            origin,
+            angle,
+            half_width: width.abs() / 2.0,
+        }
+    }
+
+    fn covers(&self, point: IVec2) -> bool {
+        if point == self.origin || self.half_width >= PI {
+            return true;
+        }
+
+        let delta = (point - self.origin).as_vec2();
+        let diff = (delta.y.atan2(delta.x) - self.angle + PI).rem_euclid(TAU) - PI;
+        diff.abs() <= self.half_width + f32::EPSILON
+    }
+}
+
+impl Iterator for GridConeIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let p = self.circle.next()?;
+            if self.covers(p) {
+                return Some(p);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::{GridShape, GridShapeIterator};
+use banana_direction::prelude::Direction;
+use banana_grid::prelude::{GridPoint, IVec2};
+
+mod iter;
+
+pub use iter::*;
+
+/// A cone (circular sector) spreading out from an origin point.
+///
+/// Angles are in radians, measured from the positive x axis towards the positive y axis.
+/// As `y` grows downwards on the grid, `Direction::South` faces `PI / 2`.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct GridCone {
+    /// The tip of the cone.
+    pub origin: IVec2,
+    /// The angle the cone is facing, in radians.
+    pub angle: f32,
+    /// The total angular width of the cone, in radians.
+    pub width: f32,
+    /// How far the cone reaches from its origin, in cells.
+    pub range: f32,
+}
+
+impl GridCone {
+    /// Create a new cone facing an angle (in radians).
+    #[inline]
+    pub fn new(origin: impl GridPoint, angle: f32, width: f32, range: f32) -> Self {
+        Self { origin: origin.as_ivec2(), angle, width, range }
+    }
+
+    /// Create a new cone facing a [`Direction`].
+    #[inline]
+    pub fn from_direction(
+        origin: impl GridPoint,
+        direction: Direction,
+        width: f32,
+        range: f32,
+    ) -> Self {
+        Self::new(origin, direction_angle(direction), width, range)
+    }
+
+    /// Turn the cone to face a [`Direction`].
+    #[inline]
+    pub fn face(&mut self, direction: Direction) {
+        self.angle = direction_angle(direction);
+    }
+}
+
+/// The angle, in radians, of a [`Direction`]'s unit offset.
+fn direction_angle(direction: Direction) -> f32 {
+    let coord = direction.coord().as_vec2();
+    coord.y.atan2(coord.x)
+}
+
+impl GridShape for GridCone {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::Cone(GridConeIter::new(self.origin, self.angle, self.width, self.range))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.origin
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.origin = pos;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::Direction;
+    use banana_grid::prelude::IVec2;
+    use banana_utils::Canvas;
+    use std::collections::HashSet;
+    use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+    fn points(shape: &impl GridShape) -> HashSet<IVec2> {
+        shape.iter().collect()
+    }
+
+    #[test]
+    fn cone_east() {
+        let cone = points(&GridCone::new([0, 0], 0.0, FRAC_PI_2, 3.0));
+        assert!(cone.contains(&IVec2::new(0, 0)));
+        assert!(cone.contains(&IVec2::new(3, 0)));
+        assert!(cone.contains(&IVec2::new(2, 2)));
+        assert!(cone.contains(&IVec2::new(2, -2)));
+        assert!(!cone.contains(&IVec2::new(1, 2)));
+        assert!(!cone.contains(&IVec2::new(-1, 0)));
+        assert!(!cone.contains(&IVec2::new(4, 0)));
+    }
+
+    #[test]
+    fn cone_from_direction() {
+        let north = points(&GridCone::from_direction([0, 0], Direction::North, FRAC_PI_2, 4.0));
+        assert!(north.contains(&IVec2::new(0, -4)));
+        assert!(!north.contains(&IVec2::new(0, 4)));
+
+        // Facing west means the angle wraps around +/- PI.
+        let west = points(&GridCone::from_direction([0, 0], Direction::West, FRAC_PI_2, 4.0));
+        assert!(west.contains(&IVec2::new(-3, 1)));
+        assert!(west.contains(&IVec2::new(-3, -1)));
+        assert!(!west.contains(&IVec2::new(3, 0)));
+
+        let mut canvas = Canvas::new([9, 9]);
+        for p in &west {
+            canvas.put(*p + IVec2::new(4, 4), '*');
+        }
+        canvas.print();
+    }
+
+    #[test]
+    fn cone_full_circle() {
+        let cone = points(&GridCone::new([0, 0], PI, TAU, 3.0));
+        let circle = points(&GridCircle::new([0, 0], 3.0));
+        assert_eq!(cone, circle);
+    }
+
+    #[test]
+    fn cone_set_pos() {
+        let mut cone = GridCone::from_direction([0, 0], Direction::SouthEast, 1.0, 5.0);
+        let before: HashSet<IVec2> = points(&cone).iter().map(|p| *p + IVec2::new(7, -2)).collect();
+        cone.set_pos(IVec2::new(7, -2));
+        assert_eq!(cone.pos(), IVec2::new(7, -2));
+        assert_eq!(points(&cone), before);
+    }
+}
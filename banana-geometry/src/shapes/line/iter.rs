@@ -0,0 +1,142 @@
+use banana_grid::prelude::{GridPoint, IVec2};
+
+/// Iterates over the cells of a line using Bresenham's algorithm.
+///
+/// Yields every point from `start` to `end` inclusive. Consecutive points may be diagonal
+/// neighbors (8-connected).
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GridLineIter {
+    curr: IVec2,
+    end: IVec2,
+    delta: IVec2,
+    step: IVec2,
+    err: i32,
+    done: bool,
+}
+
+impl GridLineIter {
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        let start = start.as_ivec2();
+        let end = end.as_ivec2();
+        let delta = IVec2::new((end.x - start.x).abs(), -(end.y - start.y).abs());
+        let step = (end - start).signum();
+        Self { curr: start, end, delta, step, err: delta.x + delta.y, done: false }
+    }
+}
+
+impl Iterator for GridLineIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let p = self.curr;
+        if p == self.end {
+            self.done = true;
+            return Some(p);
+        }
+
+        let e2 = 2 * self.err;
+        if e2 >= self.delta.y {
+            self.err += self.delta.y;
+            self.curr.x += self.step.x;
+        }
+        if e2 <= self.delta.x {
+            self.err += self.delta.x;
+            self.curr.y += self.step.y;
+        }
+        Some(p)
+    }
+}
+
+/// Iterates over the cells of a line, only ever stepping along one axis at a time.
+///
+/// Yields every point from `start` to `end` inclusive. Consecutive points are always
+/// orthogonal neighbors (4-connected).
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct GridLineOrthoIter {
+    curr: IVec2,
+    delta: IVec2,
+    step: IVec2,
+    taken: IVec2,
+    done: bool,
+}
+
+impl GridLineOrthoIter {
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        let start = start.as_ivec2();
+        let end = end.as_ivec2();
+        let delta = (end - start).abs();
+        let step = (end - start).signum();
+        Self { curr: start, delta, step, taken: IVec2::ZERO, done: false }
+    }
+}
+
+impl Iterator for GridLineOrthoIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let p = self.curr;
+        if self.taken == self.delta {
+            self.done = true;
+            return Some(p);
+        }
+
+        // Compare (0.5 + taken.x) / delta.x against (0.5 + taken.y) / delta.y, scaled to
+        // integers, and step along whichever axis crosses its next cell boundary first.
+        let x_boundary = (1 + 2 * self.taken.x) * self.delta.y;
+        let y_boundary = (1 + 2 * self.taken.y) * self.delta.x;
+        if x_boundary < y_boundary {
+            self.curr.x += self.step.x;
+            self.taken.x += 1;
+        } else {
+            self.curr.y += self.step.y;
+            self.taken.y += 1;
+        }
+        Some(p)
+    }
+}
+
+/// Iterates over the cells of a Bresenham line that is identical regardless of which
+/// endpoint it is drawn from.
+///
+/// The line is always rasterized from the lesser endpoint (ordered by `y`, then `x`), and
+/// yielded in reverse when `start` is the greater one.
+#[derive(Debug, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub enum GridLineSymmetricIter {
+    Forward(GridLineIter),
+    Reverse(std::iter::Rev<std::vec::IntoIter<IVec2>>),
+}
+
+impl GridLineSymmetricIter {
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        let start = start.as_ivec2();
+        let end = end.as_ivec2();
+        if (start.y, start.x) <= (end.y, end.x) {
+            Self::Forward(GridLineIter::new(start, end))
+        } else {
+            let points: Vec<IVec2> = GridLineIter::new(end, start).collect();
+            Self::Reverse(points.into_iter().rev())
+        }
+    }
+}
+
+impl Iterator for GridLineSymmetricIter {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            GridLineSymmetricIter::Forward(i) => i.next(),
+            GridLineSymmetricIter::Reverse(i) => i.next(),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::{GridShape, GridShapeIterator};
+use banana_grid::prelude::{GridPoint, IVec2};
+
+mod iter;
+
+pub use iter::*;
+
+/// A line between two points, rasterized with Bresenham's algorithm (8-connected).
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridLine {
+    /// The point the line starts from.
+    pub start: IVec2,
+    /// The point the line ends at.
+    pub end: IVec2,
+}
+
+impl GridLine {
+    /// Create a new line between two points.
+    #[inline]
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        Self { start: start.as_ivec2(), end: end.as_ivec2() }
+    }
+}
+
+impl GridShape for GridLine {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::Line(GridLineIter::new(self.start, self.end))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.start
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.end += pos - self.start;
+        self.start = pos;
+    }
+}
+
+/// A line between two points that only steps orthogonally (4-connected).
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridLineOrtho {
+    /// The point the line starts from.
+    pub start: IVec2,
+    /// The point the line ends at.
+    pub end: IVec2,
+}
+
+impl GridLineOrtho {
+    /// Create a new orthogonal line between two points.
+    #[inline]
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        Self { start: start.as_ivec2(), end: end.as_ivec2() }
+    }
+}
+
+impl GridShape for GridLineOrtho {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::LineOrtho(GridLineOrthoIter::new(self.start, self.end))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.start
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.end += pos - self.start;
+        self.start = pos;
+    }
+}
+
+/// A Bresenham line which covers the same cells whichever endpoint it is drawn from.
+///
+/// Useful for line of sight, where "can A see B" should always agree with "can B see A".
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridLineSymmetric {
+    /// The point the line starts from.
+    pub start: IVec2,
+    /// The point the line ends at.
+    pub end: IVec2,
+}
+
+impl GridLineSymmetric {
+    /// Create a new symmetric line between two points.
+    #[inline]
+    pub fn new(start: impl GridPoint, end: impl GridPoint) -> Self {
+        Self { start: start.as_ivec2(), end: end.as_ivec2() }
+    }
+}
+
+impl GridShape for GridLineSymmetric {
+    #[inline]
+    fn iter(&self) -> GridShapeIterator {
+        GridShapeIterator::LineSymmetric(GridLineSymmetricIter::new(self.start, self.end))
+    }
+
+    #[inline]
+    fn pos(&self) -> IVec2 {
+        self.start
+    }
+
+    #[inline]
+    fn set_pos(&mut self, pos: IVec2) {
+        self.end += pos - self.start;
+        self.start = pos;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::IVec2;
+    use banana_utils::Canvas;
+
+    fn points(shape: &impl GridShape) -> Vec<IVec2> {
+        shape.iter().collect()
+    }
+
+    #[test]
+    fn line_shallow() {
+        let line = GridLine::new([0, 0], [5, 2]);
+        let expected: Vec<IVec2> =
+            [[0, 0], [1, 0], [2, 1], [3, 1], [4, 2], [5, 2]].map(IVec2::from).to_vec();
+        assert_eq!(points(&line), expected);
+    }
+
+    #[test]
+    fn line_steep_negative() {
+        let line = GridLine::new([0, 0], [-2, -5]);
+        let expected: Vec<IVec2> =
+            [[0, 0], [0, -1], [-1, -2], [-1, -3], [-2, -4], [-2, -5]].map(IVec2::from).to_vec();
+        assert_eq!(points(&line), expected);
+    }
+
+    #[test]
+    fn line_single_point() {
+        let line = GridLine::new([3, 3], [3, 3]);
+        assert_eq!(points(&line), vec![IVec2::new(3, 3)]);
+    }
+
+    #[test]
+    fn line_ortho() {
+        let line = GridLineOrtho::new([0, 0], [3, 2]);
+        let pts = points(&line);
+        assert_eq!(pts.len(), 6);
+        assert_eq!(pts.first(), Some(&IVec2::new(0, 0)));
+        assert_eq!(pts.last(), Some(&IVec2::new(3, 2)));
+        assert!(pts.windows(2).all(|w| {
+            let d = (w[1] - w[0]).abs();
+            d.x + d.y == 1
+        }));
+
+        let mut canvas = Canvas::new([4, 3]);
+        for p in &pts {
+            canvas.put(*p, '*');
+        }
+        canvas.print();
+    }
+
+    #[test]
+    fn line_symmetric() {
+        for end in [[7, 3], [3, 7], [-4, 6], [6, -2], [-5, -5]] {
+            let forward = points(&GridLineSymmetric::new([0, 0], end));
+            let mut backward = points(&GridLineSymmetric::new(end, [0, 0]));
+            backward.reverse();
+            assert_eq!(forward, backward);
+        }
+    }
+
+    #[test]
+    fn line_set_pos() {
+        let mut line = GridLine::new([0, 0], [4, 1]);
+        line.set_pos(IVec2::new(2, 2));
+        assert_eq!(line.pos(), IVec2::new(2, 2));
+        assert_eq!(line.end, IVec2::new(6, 3));
+    }
+}
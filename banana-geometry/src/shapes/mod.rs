@@ -1,5 +1,11 @@
+mod circle;
+mod cone;
+mod line;
 mod rect;
 use banana_grid::prelude::IVec2;
+pub use circle::*;
+pub use cone::*;
+pub use line::*;
 pub use rect::*;
 
 pub trait ShapeClone {
@@ -38,6 +44,10 @@ impl PartialEq for Box<dyn GridShape> {
     }
 }
 
+/// A set of grid points, such as a circle or a line.
+///
+/// Shapes aren't tied to a grid. To lay one over a grid with an `EdgeMode`, so points
+/// past a wrapped edge land on the far side, pass its points to `EdgeMode::resolve_all`.
 pub trait GridShape: ShapeClone + Sync + Send + 'static {
     fn iter(&self) -> GridShapeIterator;
     fn pos(&self) -> IVec2;
@@ -47,12 +57,13 @@ pub trait GridShape: ShapeClone + Sync + Send + 'static {
 #[derive(Debug, Clone)]
 pub enum GridShapeIterator {
     Point(std::iter::Once<IVec2>),
-    // Circle(GridCircleIter),
-    // CircleOutline(GridCircleOutlineIter),
+    Circle(GridCircleIter),
+    CircleOutline(GridCircleOutlineIter),
     Rect(RectPointIter),
-    // Line(GridLineIter),
-    // LineOrtho(GridLineOrthoIter),
-    // Cone(GridConeIter),
+    Line(GridLineIter),
+    LineOrtho(GridLineOrthoIter),
+    LineSymmetric(GridLineSymmetricIter),
+    Cone(GridConeIter),
 }
 
 impl Iterator for GridShapeIterator {
@@ -61,12 +72,31 @@ impl Iterator for GridShapeIterator {
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             GridShapeIterator::Point(i) => i.next(),
-            // GridShapeIterator::Circle(i) => i.next(),
-            // GridShapeIterator::CircleOutline(i) => i.next(),
+            GridShapeIterator::Circle(i) => i.next(),
+            GridShapeIterator::CircleOutline(i) => i.next(),
             GridShapeIterator::Rect(i) => i.next(),
-            // GridShapeIterator::Line(i) => i.next(),
-            // GridShapeIterator::LineOrtho(i) => i.next(),
-            // GridShapeIterator::Cone(i) => i.next(),
+            GridShapeIterator::Line(i) => i.next(),
+            GridShapeIterator::LineOrtho(i) => i.next(),
+            GridShapeIterator::LineSymmetric(i) => i.next(),
+            GridShapeIterator::Cone(i) => i.next(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn edge_modes() {
+        let size = UVec2::new(8, 5);
+        let circle = GridCircle::new([0, 2], 1.5);
+        let bounded: Vec<_> = EdgeMode::<()>::Bounded.resolve_all(circle.iter(), size).collect();
+        let wrapped: Vec<_> = EdgeMode::<()>::WrapX.resolve_all(circle.iter(), size).collect();
+        assert_eq!(wrapped.len(), circle.iter().count());
+        assert!(bounded.len() < wrapped.len());
+        assert!(wrapped.contains(&IVec2::new(7, 2)));
+        assert!(wrapped.iter().all(|p| p.is_valid(size)));
+    }
+}
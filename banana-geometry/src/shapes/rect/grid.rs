@@ -0,0 +1,38 @@
+use super::Rect;
+use banana_grid::prelude::{GridNew, GridRead, GridView, GridViewMut, GridWrite, IVec2, UVec2};
+
+/// [`Rect`] windows over any grid that can be read.
+///
+/// banana-grid sits below this crate, so its own windowing methods take a corner and
+/// a size. These take the rect directly, trimmed to the grid where it sticks out.
+pub trait GridRect<T>: GridRead<T> + Sized {
+    /// A copy of the part of the grid covered by `rect`.
+    #[must_use]
+    fn crop_rect(&self, rect: Rect) -> Self
+    where
+        Self: GridNew<T>,
+        T: Clone,
+    {
+        self.crop(rect.min, size(rect))
+    }
+
+    /// Borrow the part of the grid covered by `rect`.
+    fn view_rect(&self, rect: Rect) -> GridView<'_, T, Self> {
+        self.view(rect.min, size(rect))
+    }
+
+    /// Mutably borrow the part of the grid covered by `rect`.
+    fn view_mut_rect(&mut self, rect: Rect) -> GridViewMut<'_, T, Self>
+    where
+        Self: GridWrite<T>,
+    {
+        self.view_mut(rect.min, size(rect))
+    }
+}
+
+impl<T, G: GridRead<T>> GridRect<T> for G {}
+
+/// The size of a rect, zero along any axis where it's empty.
+fn size(rect: Rect) -> UVec2 {
+    rect.size().max(IVec2::ZERO).as_uvec2()
+}
@@ -26,7 +26,7 @@ impl Iterator for RectPointIter {
     type Item = IVec2;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.curr.cmpge(self.max).any() {
+        if self.curr.cmpge(self.size).any() {
             return None;
         }
 
@@ -2,9 +2,10 @@ use banana_grid::prelude::{GridPoint, IVec2, Size2d};
 use std::collections::HashSet;
 
 mod arithmitic;
+mod grid;
 mod iter;
 
-pub use arithmitic::*;
+pub use grid::*;
 pub use iter::*;
 
 pub enum GridCorner {
@@ -201,7 +202,7 @@ impl Rect {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
-    use banana_grid::prelude::IVec2;
+    use banana_grid::prelude::{Grid, GridNew, GridRead, GridWrite, IVec2, UVec2};
     use banana_utils::Canvas;
 
     #[test]
@@ -225,18 +226,56 @@ mod tests {
         canvas.print();
     }
 
+    #[test]
+    fn iter_offset() {
+        let rect = Rect::from_corners([2, 3], [4, 6]);
+        let points: Vec<IVec2> = rect.into_iter().collect();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points.first(), Some(&IVec2::new(2, 3)));
+        assert_eq!(points.last(), Some(&IVec2::new(3, 5)));
+    }
+
+    #[test]
+    fn crop_rect() {
+        let grid = Grid::new_fn([4, 4], |p| p.x + p.y * 4);
+        let crop = grid.crop_rect(Rect::from_corners([1, 2], [3, 4]));
+        assert_eq!(crop, grid.crop([1, 2], [2, 2]));
+        assert_eq!(crop.size(), UVec2::new(2, 2));
+        assert_eq!(crop[[0, 0]], 9);
+
+        // Trimmed to the grid, and empty when the rect is.
+        let crop = grid.crop_rect(Rect::new([-1, 3], [3, 3]));
+        assert_eq!(crop.size(), UVec2::new(2, 1));
+        assert_eq!(crop[[0, 0]], 12);
+        assert_eq!(grid.crop_rect(Rect::from_corners([3, 3], [1, 1])).size(), UVec2::ZERO);
+    }
+
+    #[test]
+    fn view_rect() {
+        let mut grid = Grid::new_fn([4, 4], |p| p.x + p.y * 4);
+        let rect = Rect::from_corners([1, 2], [3, 5]);
+        let view = grid.view_rect(rect);
+        assert_eq!(view.size(), UVec2::new(2, 2));
+        assert_eq!(view.corner(), IVec2::new(1, 2));
+        assert_eq!(view.get([1, 1]), Some(&14));
+
+        grid.view_mut_rect(rect).fill(0);
+        assert_eq!(grid.iter().filter(|v| **v == 0).count(), 5);
+        assert_eq!(grid[[2, 3]], 0);
+    }
+
     #[test]
     fn test_dimensions() {
         let rect = Rect::new([0, 0], [10, 10]);
-        assert!(rect.width() == 10);
-        assert!(rect.height() == 10);
+        assert_eq!(rect.width(), 10);
+        assert_eq!(rect.height(), 10);
     }
 
     #[test]
     fn test_add() {
         let rect = Rect::from_corners([0, 0], [10, 10]) + Rect::from_corners((1, 1), (1, 1));
-        assert!(rect.min == IVec2::new(1, 1));
-        assert!(rect.max == IVec2::new(11, 11));
+        assert_eq!(rect.min, IVec2::new(1, 1));
+        assert_eq!(rect.max, IVec2::new(11, 11));
     }
 
     #[test]
@@ -0,0 +1,55 @@
+use crate::prelude::*;
+
+/// Convolves `grid` with `kernel`, each cell of the result being the sum of the cells
+/// around it weighted by the kernel.
+///
+/// The kernel is centered on each cell at half its size, rounded down. Cells past the edge
+/// are read through `grid`, so read it [with edges](GridRead::with_edges) to wrap, clamp or
+/// pad the sums; on a bounded grid they're left out.
+pub fn convolve<G>(grid: &G, kernel: &Grid<f32>) -> Grid<f32>
+where
+    G: GridRead<f32>,
+{
+    let center = (kernel.size() / 2).as_ivec2();
+    Grid::new_fn(grid.size(), |p| {
+        kernel
+            .size()
+            .iter()
+            .filter_map(|k| grid.get(p + k - center).map(|value| value * kernel[k]))
+            .sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn edges() {
+        let grid = Grid::new_fn([4, 1], |p| p.x as f32);
+        let kernel = Grid::new([3, 1], 1.0);
+        assert_eq!(convolve(&grid, &kernel).cells, [1.0, 3.0, 6.0, 5.0]);
+        assert_eq!(
+            convolve(&grid.with_edges(EdgeMode::WrapX), &kernel).cells,
+            [4.0, 3.0, 6.0, 5.0]
+        );
+        assert_eq!(
+            convolve(&grid.with_edges(EdgeMode::Clamp), &kernel).cells,
+            [1.0, 3.0, 6.0, 8.0]
+        );
+        assert_eq!(
+            convolve(&grid.with_edges(EdgeMode::Constant(10.0)), &kernel).cells,
+            [11.0, 3.0, 6.0, 15.0]
+        );
+    }
+
+    #[test]
+    fn blur() {
+        let mut grid = Grid::new([5, 5], 0.0);
+        grid[[2, 2]] = 9.0;
+        let kernel = Grid::new([3, 3], 1.0 / 9.0);
+        let blurred = convolve(&grid, &kernel);
+        assert!(blurred.cells.iter().all(|v| (*v - 1.0).abs() < 1e-6 || *v == 0.0));
+        assert_eq!(blurred.cells.iter().filter(|v| **v > 0.0).count(), 9);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::prelude::*;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// What reading a grid past its edge gives.
+///
+/// Use [`GridRead::with_edges`] to read a grid with an edge mode, which every algorithm
+/// working on a [`GridRead`] then respects.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum EdgeMode<T> {
+    /// Nothing lies past the edge.
+    #[default]
+    Bounded,
+    /// The left and right edges join up, like a world map.
+    WrapX,
+    /// The top and bottom edges join up.
+    WrapY,
+    /// Both pairs of edges join up, making the grid a torus.
+    Wrap,
+    /// Points past the edge read the closest cell on it.
+    Clamp,
+    /// Every point past the edge reads this value.
+    Constant(T),
+}
+
+impl<T> EdgeMode<T> {
+    /// Whether the mode joins up the edges along `axis`.
+    pub const fn wraps(&self, axis: Axis) -> bool {
+        matches!(
+            (self, axis),
+            (EdgeMode::Wrap, _) | (EdgeMode::WrapX, Axis::X) | (EdgeMode::WrapY, Axis::Y)
+        )
+    }
+
+    /// The cell of a grid of `size` that `point` reads, or `None` if it lies past an edge
+    /// that isn't wrapped or clamped.
+    ///
+    /// Points past the edge of a [`Constant`](EdgeMode::Constant) grid read no cell.
+    pub fn resolve(&self, point: impl GridPoint, size: impl Size2d) -> Option<IVec2> {
+        if size.count() == 0 {
+            return None;
+        }
+
+        let point = point.as_ivec2();
+        let resolved = match self {
+            EdgeMode::Bounded | EdgeMode::Constant(_) => point,
+            EdgeMode::Clamp => point.clamp(IVec2::ZERO, size.as_ivec2() - 1),
+            _ => {
+                let wrapped = point.normalize(size);
+                IVec2::new(
+                    if self.wraps(Axis::X) { wrapped.x } else { point.x },
+                    if self.wraps(Axis::Y) { wrapped.y } else { point.y },
+                )
+            }
+        };
+        resolved.is_valid(size).then_some(resolved)
+    }
+
+    /// Resolves every point in `points`, leaving out those that read no cell, so shapes
+    /// and other point sets can be laid over a grid with this mode.
+    ///
+    /// Clamped or wrapped points may resolve to the same cell more than once.
+    pub fn resolve_all<'a, I>(
+        &'a self,
+        points: I,
+        size: impl Size2d,
+    ) -> impl Iterator<Item = IVec2> + 'a
+    where
+        I: IntoIterator,
+        I::Item: GridPoint,
+        I::IntoIter: 'a,
+    {
+        let size = size.as_uvec2();
+        points.into_iter().filter_map(move |p| self.resolve(p, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn resolve() {
+        let size = UVec2::new(4, 3);
+        let bounded = EdgeMode::<()>::Bounded;
+        assert_eq!(bounded.resolve([3, 2], size), Some(IVec2::new(3, 2)));
+        assert_eq!(bounded.resolve([4, 2], size), None);
+        assert_eq!(EdgeMode::Constant(0).resolve([-1, 0], size), None);
+
+        let wrap_x = EdgeMode::<()>::WrapX;
+        assert_eq!(wrap_x.resolve([-1, 1], size), Some(IVec2::new(3, 1)));
+        assert_eq!(wrap_x.resolve([9, 1], size), Some(IVec2::new(1, 1)));
+        assert_eq!(wrap_x.resolve([0, 3], size), None);
+        assert_eq!(EdgeMode::<()>::WrapY.resolve([0, -1], size), Some(IVec2::new(0, 2)));
+        assert_eq!(EdgeMode::<()>::WrapY.resolve([4, 0], size), None);
+        assert_eq!(EdgeMode::<()>::Wrap.resolve([-5, 7], size), Some(IVec2::new(3, 1)));
+
+        assert_eq!(EdgeMode::<()>::Clamp.resolve([-5, 7], size), Some(IVec2::new(0, 2)));
+        assert_eq!(EdgeMode::<()>::Clamp.resolve([1, 1], UVec2::ZERO), None);
+    }
+
+    #[test]
+    fn resolve_all() {
+        let points = [IVec2::new(-1, 0), IVec2::new(0, 0), IVec2::new(0, -1)];
+        let wrapped: Vec<_> = EdgeMode::<()>::WrapX.resolve_all(points, [4, 3]).collect();
+        assert_eq!(wrapped, [IVec2::new(3, 0), IVec2::new(0, 0)]);
+    }
+}
@@ -1,54 +1,38 @@
 use crate::prelude::*;
 use std::ops::{Bound, RangeBounds};
 
-/// The [Grid] trait abstracts over containers of [Clone] and [Copy] items laid out in a
-/// rectangle with a certain [width](Self::width) and [height](Self::height).
-pub trait GridLike<T> {
-    fn new<S>(size: S, new_value: T) -> Self
-    where
-        S: Size2d,
-        Self: Sized;
-
-    fn new_fn<S, F>(size: S, f: F) -> Self
-    where
-        S: Size2d,
-        F: FnMut(IVec2) -> T,
-        Self: Sized;
-
-    fn new_grid_map<G, U, F>(grid: G, f: F) -> Self
-    where
-        U: Clone,
-        Self: Sized,
-        G: GridLike<U>,
-        F: FnMut(&U) -> T;
-
-    fn new_clone<S>(size: S, value: T) -> Self
-    where
-        T: Clone,
-        S: Size2d;
-
-    fn new_default<S>(size: S) -> Self
-    where
-        T: Default,
-        S: Size2d;
+/// Read access to items laid out in a rectangle with a certain [width](Self::width) and
+/// [height](Self::height).
+///
+/// Only [`size`](Self::size) and [`get`](Self::get) have to be implemented, which lets
+/// views, chunked worlds and sparse maps be read by the same algorithms as [`Grid`].
+pub trait GridRead<T> {
+    fn size(&self) -> UVec2;
 
-    fn new_copy<S>(size: S, value: T) -> Self
+    /// Gets the item at a point, or `None` if it is out of bounds.
+    fn get<P>(&self, point: P) -> Option<&T>
     where
-        T: Copy,
-        S: Size2d;
+        P: GridPoint;
 
-    fn data(&self) -> &[T];
+    #[inline]
+    fn width(&self) -> u32 {
+        self.size().width()
+    }
 
-    fn fill(&mut self, value: T)
-    where
-        T: Clone;
+    #[inline]
+    fn height(&self) -> u32 {
+        self.size().height()
+    }
 
-    fn width(&self) -> u32;
-    fn height(&self) -> u32;
-    fn size(&self) -> UVec2;
+    #[inline]
+    fn len(&self) -> usize {
+        self.size().count()
+    }
 
-    fn len(&self) -> usize;
-    fn is_empty(&self) -> bool;
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
     /// Tests whether a point is in bounds.
     fn in_bounds<P>(&self, point: P) -> bool
@@ -119,31 +103,401 @@ pub trait GridLike<T> {
         [start, end]
     }
 
-    ///////////////////////////////////////////////////////////////////////////
-    // GridPoint Getters
-    ///////////////////////////////////////////////////////////////////////////
+    /// Gets the item at a point, panicking if it is out of bounds.
+    fn get_checked<P>(&self, point: P) -> &T
+    where
+        P: GridPoint,
+    {
+        let pos = point.as_ivec2();
+        self.get(pos).unwrap_or_else(|| panic!("{pos} is out of bounds of {}", self.size()))
+    }
+
+    /// Counts how many of the eight cells around a point hold `val`, cells outside the
+    /// grid not counting.
+    ///
+    /// `GridNeighbors` in banana-direction iterates over neighborhoods with their
+    /// directions.
+    fn count_neighbors<P>(&self, point: P, val: T) -> usize
+    where
+        P: GridPoint,
+        T: std::cmp::PartialEq,
+    {
+        let mut neighbors = 0;
+        for iy in -1..=1 {
+            for ix in -1..=1 {
+                if !(ix == 0 && iy == 0) && self.get((point.x() + ix, point.y() + iy)) == Some(&val)
+                {
+                    neighbors += 1;
+                }
+            }
+        }
+        neighbors
+    }
 
-    // No bounds Checking
+    /// The point whose cell [`get`](Self::get) reads for `point`, or `None` if there is
+    /// none.
+    ///
+    /// This is `point` itself when it is in bounds. Grids read with an [`EdgeMode`] map
+    /// points past their edge back onto the grid, so algorithms marking the cells they
+    /// reach should mark the resolved point.
+    fn resolve<P>(&self, point: P) -> Option<IVec2>
+    where
+        P: GridPoint,
+    {
+        self.in_bounds(point).then(|| point.as_ivec2())
+    }
+
+    /// Read the grid with an [`EdgeMode`], deciding what points past its edge read.
+    fn with_edges(&self, mode: EdgeMode<T>) -> EdgeView<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        EdgeView::new(self, mode)
+    }
+
+    /// Borrow the part of the grid with its top left corner at `corner` and of `size`,
+    /// trimmed to the grid where it sticks out.
+    fn view(&self, corner: impl GridPoint, size: impl Size2d) -> GridView<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        GridView::new(self, corner, size)
+    }
+}
+
+impl<T, G: GridRead<T> + ?Sized> GridRead<T> for &G {
+    #[inline]
+    fn size(&self) -> UVec2 {
+        (**self).size()
+    }
+
+    #[inline]
     fn get<P>(&self, point: P) -> Option<&T>
     where
-        P: GridPoint;
-    fn get_mut<P>(&mut self, point: P) -> Option<&mut T>
+        P: GridPoint,
+    {
+        (**self).get(point)
+    }
+
+    #[inline]
+    fn resolve<P>(&self, point: P) -> Option<IVec2>
     where
-        P: GridPoint;
+        P: GridPoint,
+    {
+        (**self).resolve(point)
+    }
+}
 
-    // Bounds Checking
-    fn get_checked<P>(&self, point: P) -> &T
+/// Write access to the items of a [`GridRead`].
+pub trait GridWrite<T>: GridRead<T> {
+    /// Gets the item at a point mutably, or `None` if it is out of bounds.
+    fn get_mut<P>(&mut self, point: P) -> Option<&mut T>
     where
         P: GridPoint;
+
+    /// Gets the item at a point mutably, panicking if it is out of bounds.
     fn get_mut_checked<P>(&mut self, point: P) -> &mut T
     where
-        P: GridPoint;
+        P: GridPoint,
+    {
+        let pos = point.as_ivec2();
+        let size = self.size();
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos} is out of bounds of {size}"))
+    }
+
+    /// Sets every item to `value`.
+    fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for point in self.size().iter() {
+            *self.get_mut_checked(point) = value.clone();
+        }
+    }
+
+    /// Mutably borrow the part of the grid with its top left corner at `corner` and of
+    /// `size`, trimmed to the grid where it sticks out.
+    fn view_mut(&mut self, corner: impl GridPoint, size: impl Size2d) -> GridViewMut<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        GridViewMut::new(self, corner, size)
+    }
+}
+
+/// Grids that can be created, from a function of each point or from another grid.
+///
+/// Only [`new_fn`](Self::new_fn) has to be implemented.
+pub trait GridNew<T>: GridRead<T> + Sized {
+    fn new_fn<S, F>(size: S, f: F) -> Self
+    where
+        S: Size2d,
+        F: FnMut(IVec2) -> T;
+
+    fn new<S>(size: S, new_value: T) -> Self
+    where
+        S: Size2d,
+        T: Clone,
+    {
+        Self::new_fn(size, |_| new_value.clone())
+    }
+
+    /// A grid of the same size as `grid`, with each item mapped by `f`.
+    fn new_grid_map<G, U, F>(grid: G, mut f: F) -> Self
+    where
+        G: GridRead<U>,
+        F: FnMut(&U) -> T,
+    {
+        Self::new_fn(grid.size(), |p| f(grid.get_checked(p)))
+    }
+
+    fn new_clone<S>(size: S, value: T) -> Self
+    where
+        T: Clone,
+        S: Size2d,
+    {
+        Self::new_fn(size, |_| value.clone())
+    }
+
+    fn new_default<S>(size: S) -> Self
+    where
+        T: Default,
+        S: Size2d,
+    {
+        Self::new_fn(size, |_| T::default())
+    }
+
+    fn new_copy<S>(size: S, value: T) -> Self
+    where
+        T: Copy,
+        S: Size2d,
+    {
+        Self::new_fn(size, |_| value)
+    }
 
     ///////////////////////////////////////////////////////////////////////////
-    // GridPoint Iters
+    // Transforms
     ///////////////////////////////////////////////////////////////////////////
-    fn count_neighbors<P>(&self, point: P, val: T) -> usize
+
+    /// A copy of the grid turned a quarter turn clockwise.
+    #[must_use]
+    fn rotate90(&self) -> Self
     where
-        P: GridPoint,
-        T: std::cmp::PartialEq;
+        T: Clone,
+    {
+        let height = self.height() as i32;
+        Self::new_fn(UVec2::new(self.height(), self.width()), |p| {
+            self.get_checked(IVec2::new(p.y, height - 1 - p.x)).clone()
+        })
+    }
+
+    /// A copy of the grid turned half a turn.
+    #[must_use]
+    fn rotate180(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last = self.size().as_ivec2() - IVec2::ONE;
+        Self::new_fn(self.size(), |p| self.get_checked(last - p).clone())
+    }
+
+    /// A copy of the grid turned a quarter turn counter-clockwise.
+    #[must_use]
+    fn rotate270(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width() as i32;
+        Self::new_fn(UVec2::new(self.height(), self.width()), |p| {
+            self.get_checked(IVec2::new(width - 1 - p.y, p.x)).clone()
+        })
+    }
+
+    /// A copy of the grid with its rows turned into columns.
+    #[must_use]
+    fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::new_fn(UVec2::new(self.height(), self.width()), |p| {
+            self.get_checked(IVec2::new(p.y, p.x)).clone()
+        })
+    }
+
+    /// A mirrored copy of the grid: [`Axis::X`] swaps left and right, [`Axis::Y`] swaps
+    /// top and bottom.
+    #[must_use]
+    fn flip(&self, axis: Axis) -> Self
+    where
+        T: Clone,
+    {
+        let last = self.size().as_ivec2() - IVec2::ONE;
+        Self::new_fn(self.size(), |p| {
+            let source = match axis {
+                Axis::X => IVec2::new(last.x - p.x, p.y),
+                Axis::Y => IVec2::new(p.x, last.y - p.y),
+            };
+            self.get_checked(source).clone()
+        })
+    }
+
+    /// A copy of the part of the grid with its top left corner at `corner` and of
+    /// `size`, trimmed to the grid where it sticks out.
+    #[must_use]
+    fn crop(&self, corner: impl GridPoint, size: impl Size2d) -> Self
+    where
+        T: Clone,
+    {
+        let min = corner.as_ivec2().clamp(IVec2::ZERO, self.size().as_ivec2());
+        let max = (corner.as_ivec2() + size.as_ivec2()).clamp(min, self.size().as_ivec2());
+        Self::new_fn((max - min).as_uvec2(), |p| self.get_checked(min + p).clone())
+    }
+
+    /// A copy of the grid of another size, anchored at the top left corner. New cells
+    /// are set to `fill`.
+    #[must_use]
+    fn resize(&self, size: impl Size2d, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new_fn(size, |p| self.get(p).unwrap_or(&fill).clone())
+    }
+
+    /// A copy of the grid with `border` cells of `fill` added on every side.
+    #[must_use]
+    fn pad(&self, border: u32, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let offset = IVec2::splat(border as i32);
+        Self::new_fn(self.size() + UVec2::splat(border * 2), |p| {
+            self.get(p - offset).unwrap_or(&fill).clone()
+        })
+    }
+}
+
+/// Grids that can be read, written and created, like [`Grid`] and [`Grid2D`].
+///
+/// Implemented for every type implementing [`GridRead`], [`GridWrite`] and [`GridNew`].
+pub trait GridLike<T>: GridRead<T> + GridWrite<T> + GridNew<T> {}
+
+impl<T, G> GridLike<T> for G where G: GridRead<T> + GridWrite<T> + GridNew<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn grids() -> (Grid<u32>, Grid2D<u32>) {
+        let value = |p: IVec2| (p.y * 10 + p.x) as u32;
+        (Grid::new_fn([4, 3], value), Grid2D::new_fn([4, 3], value))
+    }
+
+    fn rows(grid: &Grid<u32>) -> Vec<Vec<u32>> {
+        grid.rows().map(<[u32]>::to_vec).collect()
+    }
+
+    /// Both kinds of grid hold the same cells at the same points.
+    fn assert_agree(grid: &Grid<u32>, grid_2d: &Grid2D<u32>) {
+        assert_eq!(grid.size(), grid_2d.size());
+        for p in grid.size().iter() {
+            assert_eq!(grid[p], grid_2d[p], "{p}");
+        }
+    }
+
+    #[test]
+    fn rotate() {
+        let (grid, grid_2d) = grids();
+        assert_eq!(rows(&grid.rotate90()), [[20, 10, 0], [21, 11, 1], [22, 12, 2], [23, 13, 3]]);
+        assert_eq!(rows(&grid.rotate180()), [[23, 22, 21, 20], [13, 12, 11, 10], [3, 2, 1, 0]]);
+        assert_eq!(rows(&grid.rotate270()), [[3, 13, 23], [2, 12, 22], [1, 11, 21], [0, 10, 20]]);
+        assert_eq!(grid.rotate90().rotate90(), grid.rotate180());
+        assert_eq!(grid.rotate90().rotate270(), grid);
+
+        assert_agree(&grid.rotate90(), &grid_2d.rotate90());
+        assert_agree(&grid.rotate180(), &grid_2d.rotate180());
+        assert_agree(&grid.rotate270(), &grid_2d.rotate270());
+    }
+
+    #[test]
+    fn transpose_and_flip() {
+        let (grid, grid_2d) = grids();
+        assert_eq!(rows(&grid.transpose()), [[0, 10, 20], [1, 11, 21], [2, 12, 22], [3, 13, 23]]);
+        assert_eq!(rows(&grid.flip(Axis::X)), [[3, 2, 1, 0], [13, 12, 11, 10], [23, 22, 21, 20]]);
+        assert_eq!(rows(&grid.flip(Axis::Y)), [[20, 21, 22, 23], [10, 11, 12, 13], [0, 1, 2, 3]]);
+        assert_eq!(grid.flip(Axis::X).flip(Axis::Y), grid.rotate180());
+        assert_eq!(grid.transpose().flip(Axis::X), grid.rotate90());
+
+        assert_agree(&grid.transpose(), &grid_2d.transpose());
+        assert_agree(&grid.flip(Axis::X), &grid_2d.flip(Axis::X));
+        assert_agree(&grid.flip(Axis::Y), &grid_2d.flip(Axis::Y));
+    }
+
+    #[test]
+    fn crop() {
+        let (grid, grid_2d) = grids();
+        assert_eq!(rows(&grid.crop([1, 1], [2, 2])), [[11, 12], [21, 22]]);
+        assert_eq!(rows(&grid.crop([2, -1], [5, 3])), [[2, 3], [12, 13]]);
+        assert!(grid.crop([5, 5], [2, 2]).is_empty());
+
+        assert_agree(&grid.crop([1, 1], [2, 2]), &grid_2d.crop([1, 1], [2, 2]));
+        assert_agree(&grid.crop([2, -1], [5, 3]), &grid_2d.crop([2, -1], [5, 3]));
+    }
+
+    #[test]
+    fn resize_and_pad() {
+        let (grid, grid_2d) = grids();
+        assert_eq!(rows(&grid.resize([2, 4], 9)), [[0, 1], [10, 11], [20, 21], [9, 9]]);
+        let padded = grid.pad(1, 9);
+        assert_eq!(padded.size(), UVec2::new(6, 5));
+        assert_eq!(rows(&padded)[0], [9; 6]);
+        assert_eq!(rows(&padded)[2], [9, 10, 11, 12, 13, 9]);
+        assert_eq!(padded.crop([1, 1], grid.size()), grid);
+
+        assert_agree(&grid.resize([2, 4], 9), &grid_2d.resize([2, 4], 9));
+        assert_agree(&grid.resize([6, 1], 9), &grid_2d.resize([6, 1], 9));
+        assert_agree(&grid.pad(2, 9), &grid_2d.pad(2, 9));
+    }
+
+    /// Only answers `get`, like a chunked world or a sparse map would.
+    struct Sparse(std::collections::HashMap<IVec2, u32>);
+
+    impl GridRead<u32> for Sparse {
+        fn size(&self) -> UVec2 {
+            UVec2::new(4, 3)
+        }
+
+        fn get<P: GridPoint>(&self, point: P) -> Option<&u32> {
+            self.0.get(&point.as_ivec2())
+        }
+    }
+
+    #[test]
+    fn read_only() {
+        let sparse = Sparse([(IVec2::new(1, 2), 7), (IVec2::new(3, 0), 5)].into_iter().collect());
+        assert_eq!(sparse.len(), 12);
+        assert!(sparse.in_bounds([3, 2]));
+        assert!(!sparse.in_bounds([4, 0]));
+        assert_eq!(*sparse.get_checked([1, 2]), 7);
+        assert_eq!(sparse.view([1, 0], [3, 3]).get([0, 2]), Some(&7));
+
+        let (grid, grid_2d) = grids();
+        let dense = Grid::new_fn(sparse.size(), |p| sparse.get(p).copied().unwrap_or(0));
+        assert_eq!(dense.cells.iter().sum::<u32>(), 12);
+        assert_agree(&Grid::new_grid_map(&grid_2d, |v| *v), &grid_2d);
+        assert_agree(&grid, &Grid2D::new_grid_map(grid.view([0, 0], grid.size()), |v| *v));
+    }
+
+    #[test]
+    fn count_neighbors_at_edges() {
+        let grid = Grid::new_fn([3, 3], |p| p.x == 1 || p.y == 1);
+        assert_eq!(grid.count_neighbors([1, 1], true), 4);
+        assert_eq!(grid.count_neighbors([0, 0], true), 3);
+        assert_eq!(grid.count_neighbors([2, 2], false), 0);
+        assert_eq!(grid.count_neighbors([-1, 1], true), 1);
+
+        let grid_2d = Grid2D::new_fn([3, 3], |p| p.x == 1 || p.y == 1);
+        for p in grid.size().iter() {
+            assert_eq!(grid.count_neighbors(p, true), grid_2d.count_neighbors(p, true), "{p}");
+        }
+    }
 }
@@ -71,6 +71,22 @@ pub trait GridPoint: Clone + Copy {
         x >= 0 && y >= 0 && x < size.width() as i32 && y < size.height() as i32
     }
 
+    /// The chunk of a grid split into chunks of `chunk_size` that the point lies in.
+    ///
+    /// Chunks are counted from the one whose top left corner is `(0, 0)`, rounding down, so
+    /// points left of or above the origin land in negative chunks. The point within the
+    /// chunk is [`normalize`](Self::normalize)d by `chunk_size`.
+    #[inline]
+    fn chunk<S>(&self, chunk_size: S) -> IVec2
+    where
+        S: Size2d,
+    {
+        IVec2::new(
+            self.x().div_euclid(chunk_size.width() as i32),
+            self.y().div_euclid(chunk_size.height() as i32),
+        )
+    }
+
     #[inline(always)]
     fn normalize_part(value: i32, size: u32) -> i32 {
         let value = value % size as i32;
@@ -0,0 +1,445 @@
+use crate::prelude::*;
+use std::{cell::Cell, collections::HashMap, fmt};
+
+/// Makes the chunk at a chunk coordinate.
+type Generate<T> = Box<dyn FnMut(IVec2) -> Grid<T>>;
+/// Receives the chunks evicted while they were dirty.
+type OnEvict<T> = Box<dyn FnMut(IVec2, Grid<T>)>;
+
+/// When a [`ChunkedGrid`] drops chunks it has loaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Eviction {
+    /// Chunks stay loaded until they are unloaded by hand.
+    #[default]
+    Never,
+    /// Keep at most this many chunks loaded, unloading the one used least recently to make
+    /// room for a new one.
+    LeastRecentlyUsed(usize),
+}
+
+struct Chunk<T> {
+    grid: Grid<T>,
+    last_used: Cell<u64>,
+    /// Whether the chunk was borrowed mutably since it was generated or marked clean.
+    dirty: bool,
+}
+
+/// An unbounded grid made of equally sized [`Grid`] chunks, generated the first time a
+/// point inside them is written or loaded.
+///
+/// Points can be negative: the chunk at `(0, 0)` covers `(0, 0)` up to the chunk size,
+/// and the chunk at `(-1, 0)` the cells just left of it. Reading an unloaded chunk with
+/// [`get`](GridRead::get) gives `None` rather than generating it.
+///
+/// As a [`GridRead`], the grid reaches from `(0, 0)` to the far corner of its loaded
+/// chunks, so algorithms run over the loaded cells at positive points. Cells of loaded
+/// chunks at negative points can still be read, but aren't [in
+/// bounds](GridRead::in_bounds), just like the cells past the edge of a grid read with
+/// [`EdgeMode::Constant`].
+///
+/// A chunk becomes dirty when it is borrowed mutably. Evicting a dirty chunk hands it to
+/// the callback set with [`with_on_evict`](Self::with_on_evict) so it can be saved,
+/// while clean chunks are dropped, as they can be generated again. Without a callback,
+/// dirty chunks are never evicted, so more chunks than the eviction policy allows can
+/// stay loaded until they are [marked clean](Self::mark_clean) or unloaded by hand.
+pub struct ChunkedGrid<T> {
+    chunk_size: UVec2,
+    chunks: HashMap<IVec2, Chunk<T>>,
+    generate: Generate<T>,
+    eviction: Eviction,
+    on_evict: Option<OnEvict<T>>,
+    clock: Cell<u64>,
+}
+
+impl<T> fmt::Debug for ChunkedGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedGrid")
+            .field("chunk_size", &self.chunk_size)
+            .field("loaded", &self.chunks.len())
+            .field("eviction", &self.eviction)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ChunkedGrid<T> {
+    /// A world of chunks of `chunk_size`, each made by `generate` from its chunk
+    /// coordinate when first needed.
+    pub fn new<F>(chunk_size: impl Size2d, generate: F) -> Self
+    where
+        F: FnMut(IVec2) -> Grid<T> + 'static,
+    {
+        assert!(chunk_size.count() > 0, "chunks can't be empty");
+        Self {
+            chunk_size: chunk_size.as_uvec2(),
+            chunks: HashMap::new(),
+            generate: Box::new(generate),
+            eviction: Eviction::default(),
+            on_evict: None,
+            clock: Cell::new(0),
+        }
+    }
+
+    /// A world where every cell of a new chunk starts as a clone of `value`.
+    pub fn new_clone(chunk_size: impl Size2d, value: T) -> Self
+    where
+        T: Clone + 'static,
+    {
+        let size = chunk_size.as_uvec2();
+        Self::new(size, move |_| Grid::new_clone(size, value.clone()))
+    }
+
+    #[must_use]
+    pub fn with_eviction(mut self, eviction: Eviction) -> Self {
+        self.eviction = eviction;
+        self.evict(None);
+        self
+    }
+
+    /// Hand every dirty chunk that gets evicted to `on_evict`, with its chunk coordinate.
+    #[must_use]
+    pub fn with_on_evict<F>(mut self, on_evict: F) -> Self
+    where
+        F: FnMut(IVec2, Grid<T>) + 'static,
+    {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    #[inline]
+    pub fn chunk_size(&self) -> UVec2 {
+        self.chunk_size
+    }
+
+    #[inline]
+    pub fn eviction(&self) -> Eviction {
+        self.eviction
+    }
+
+    /// The number of chunks loaded.
+    #[inline]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// The chunk coordinate of the chunk holding a point.
+    #[inline]
+    pub fn chunk_of(&self, point: impl GridPoint) -> IVec2 {
+        point.chunk(self.chunk_size)
+    }
+
+    /// The point of the world at the top left corner of a chunk.
+    #[inline]
+    pub fn chunk_corner(&self, chunk: impl GridPoint) -> IVec2 {
+        chunk.as_ivec2() * self.chunk_size.as_ivec2()
+    }
+
+    #[inline]
+    pub fn is_loaded(&self, chunk: impl GridPoint) -> bool {
+        self.chunks.contains_key(&chunk.as_ivec2())
+    }
+
+    /// Whether a loaded chunk was borrowed mutably since it was generated or last
+    /// [marked clean](Self::mark_clean).
+    pub fn is_dirty(&self, chunk: impl GridPoint) -> bool {
+        self.chunks.get(&chunk.as_ivec2()).is_some_and(|chunk| chunk.dirty)
+    }
+
+    /// Marks a chunk as clean, for once its changes have been saved.
+    pub fn mark_clean(&mut self, chunk: impl GridPoint) {
+        if let Some(chunk) = self.chunks.get_mut(&chunk.as_ivec2()) {
+            chunk.dirty = false;
+        }
+    }
+
+    /// A loaded chunk, or `None` if it hasn't been generated or was evicted.
+    pub fn chunk(&self, chunk: impl GridPoint) -> Option<&Grid<T>> {
+        self.chunks.get(&chunk.as_ivec2()).map(|chunk| self.touch(chunk))
+    }
+
+    /// A chunk, generated first if it isn't loaded. The chunk becomes dirty.
+    pub fn load(&mut self, chunk: impl GridPoint) -> &mut Grid<T> {
+        let coord = chunk.as_ivec2();
+        if !self.chunks.contains_key(&coord) {
+            let grid = (self.generate)(coord);
+            assert_eq!(grid.size(), self.chunk_size, "chunk {coord} was generated the wrong size");
+            self.chunks.insert(coord, Chunk { grid, last_used: Cell::new(0), dirty: false });
+            self.evict(Some(coord));
+        }
+
+        let tick = self.tick();
+        let chunk = self.chunks.get_mut(&coord).expect("the chunk was just loaded");
+        *chunk.last_used.get_mut() = tick;
+        chunk.dirty = true;
+        &mut chunk.grid
+    }
+
+    /// Unloads a chunk, handing it back if it was loaded.
+    pub fn unload(&mut self, chunk: impl GridPoint) -> Option<Grid<T>> {
+        self.chunks.remove(&chunk.as_ivec2()).map(|chunk| chunk.grid)
+    }
+
+    /// Unloads every chunk for which `keep` returns false.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(IVec2, &Grid<T>) -> bool,
+    {
+        self.chunks.retain(|coord, chunk| keep(*coord, &chunk.grid));
+    }
+
+    /// The cell at a point mutably, generating its chunk if it isn't loaded.
+    pub fn get_or_load(&mut self, point: impl GridPoint) -> &mut T {
+        let local = point.normalize(self.chunk_size);
+        self.load(self.chunk_of(point)).get_mut_checked(local)
+    }
+
+    /// Sets the cell at a point, generating its chunk if it isn't loaded.
+    pub fn set(&mut self, point: impl GridPoint, value: T) {
+        *self.get_or_load(point) = value;
+    }
+
+    /// Iterate over the loaded chunks with their chunk coordinates, in no particular
+    /// order.
+    pub fn chunks(&self) -> impl Iterator<Item = (IVec2, &Grid<T>)> {
+        self.chunks.iter().map(|(coord, chunk)| (*coord, &chunk.grid))
+    }
+
+    /// Iterate mutably over the loaded chunks with their chunk coordinates, in no
+    /// particular order. Every loaded chunk becomes dirty.
+    pub fn chunks_mut(&mut self) -> impl Iterator<Item = (IVec2, &mut Grid<T>)> {
+        self.chunks.iter_mut().map(|(coord, chunk)| {
+            chunk.dirty = true;
+            (*coord, &mut chunk.grid)
+        })
+    }
+
+    /// Iterate over every loaded cell with its point in the world, a chunk at a time.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.chunks().flat_map(move |(coord, grid)| {
+            let corner = self.chunk_corner(coord);
+            grid.size().iter().map(move |p| (corner + p, grid.get_checked(p)))
+        })
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.set(self.clock.get() + 1);
+        self.clock.get()
+    }
+
+    /// Marks a chunk as used, for eviction.
+    fn touch<'a>(&self, chunk: &'a Chunk<T>) -> &'a Grid<T> {
+        chunk.last_used.set(self.tick());
+        &chunk.grid
+    }
+
+    /// Unloads chunks until the eviction policy is met, never unloading `keep`, nor dirty
+    /// chunks if there's no callback to save them.
+    fn evict(&mut self, keep: Option<IVec2>) {
+        let Eviction::LeastRecentlyUsed(max) = self.eviction else { return };
+        while self.chunks.len() > max {
+            let oldest = self
+                .chunks
+                .iter()
+                .filter(|(coord, chunk)| {
+                    Some(**coord) != keep && (!chunk.dirty || self.on_evict.is_some())
+                })
+                .min_by_key(|(_, chunk)| chunk.last_used.get())
+                .map(|(coord, _)| *coord);
+            let Some(oldest) = oldest else { return };
+            let chunk = self.chunks.remove(&oldest).expect("the chunk is loaded");
+            if let (true, Some(on_evict)) = (chunk.dirty, &mut self.on_evict) {
+                on_evict(oldest, chunk.grid);
+            }
+        }
+    }
+}
+
+impl<T> GridRead<T> for ChunkedGrid<T> {
+    /// The size reaching from `(0, 0)` to the far corner of the loaded chunks, zero if no
+    /// chunk at a positive point is loaded.
+    fn size(&self) -> UVec2 {
+        let far = self
+            .chunks
+            .keys()
+            .map(|coord| self.chunk_corner(*coord + 1))
+            .fold(IVec2::ZERO, IVec2::max);
+        far.as_uvec2()
+    }
+
+    /// The cell at a point, or `None` if its chunk isn't loaded.
+    fn get<P>(&self, point: P) -> Option<&T>
+    where
+        P: GridPoint,
+    {
+        let grid = self.chunk(self.chunk_of(point))?;
+        grid.get(point.normalize(self.chunk_size))
+    }
+
+    /// Whether a point is at a positive point of a loaded chunk.
+    fn in_bounds<P>(&self, point: P) -> bool
+    where
+        P: GridPoint,
+    {
+        point.as_ivec2().cmpge(IVec2::ZERO).all() && self.is_loaded(self.chunk_of(point))
+    }
+}
+
+impl<T> GridWrite<T> for ChunkedGrid<T> {
+    /// The cell at a point mutably, or `None` if its chunk isn't loaded. The chunk
+    /// becomes dirty.
+    fn get_mut<P>(&mut self, point: P) -> Option<&mut T>
+    where
+        P: GridPoint,
+    {
+        let coord = self.chunk_of(point);
+        let local = point.normalize(self.chunk_size);
+        let tick = self.tick();
+        let chunk = self.chunks.get_mut(&coord)?;
+        *chunk.last_used.get_mut() = tick;
+        chunk.dirty = true;
+        chunk.grid.get_mut(local)
+    }
+
+    /// Sets every cell of the loaded chunks to `value`, making them all dirty.
+    fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for chunk in self.chunks.values_mut() {
+            chunk.grid.fill(value.clone());
+            chunk.dirty = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    /// Every cell starts as the chunk it's in, packed as `x * 100 + y`.
+    fn world() -> ChunkedGrid<i32> {
+        ChunkedGrid::new([4, 3], |chunk| Grid::new([4, 3], chunk.x * 100 + chunk.y))
+    }
+
+    #[test]
+    fn negative_points() {
+        assert_eq!(IVec2::new(-1, 0).chunk([4, 3]), IVec2::new(-1, 0));
+        assert_eq!(IVec2::new(-4, -3).chunk([4, 3]), IVec2::new(-1, -1));
+        assert_eq!(IVec2::new(-5, 3).chunk([4, 3]), IVec2::new(-2, 1));
+        assert_eq!(IVec2::new(-5, 3).normalize([4, 3]), IVec2::new(3, 0));
+
+        let mut world = world();
+        assert_eq!(world.get([-1, -1]), None);
+        assert_eq!(*world.get_or_load([-1, -1]), -101);
+        assert_eq!(*world.get_or_load([-5, 3]), -199);
+        world.set([-5, 3], 7);
+        assert_eq!(world.get([-5, 3]), Some(&7));
+        assert_eq!(world.chunk([-2, 1]).unwrap()[[3, 0]], 7);
+        assert_eq!(world.chunk_corner([-2, 1]), IVec2::new(-8, 3));
+    }
+
+    #[test]
+    fn lazy() {
+        let mut world = world();
+        assert_eq!(world.chunk_count(), 0);
+        world.set([5, 1], 1);
+        assert!(world.is_loaded([1, 0]));
+        assert_eq!(world.chunk_count(), 1);
+        *world.get_mut([4, 0]).unwrap() = 2;
+        assert!(world.get_mut([0, 0]).is_none());
+
+        world.load([0, 0]);
+        let mut loaded: Vec<_> = world.chunks().map(|(coord, _)| coord).collect();
+        loaded.sort_by_key(|c| (c.y, c.x));
+        assert_eq!(loaded, [IVec2::new(0, 0), IVec2::new(1, 0)]);
+        assert_eq!(world.iter().count(), 24);
+        assert_eq!(world.iter().find(|(_, v)| **v == 1).unwrap().0, IVec2::new(5, 1));
+
+        assert_eq!(world.unload([1, 0]).unwrap()[[0, 0]], 2);
+        assert_eq!(world.get([5, 1]), None);
+        assert_eq!(world.get_or_load([5, 1]), &100);
+    }
+
+    #[test]
+    fn grid_traits() {
+        fn total(grid: &impl GridRead<i32>) -> i32 {
+            grid.size().iter().filter_map(|p| grid.get(p)).sum()
+        }
+
+        let mut world = world();
+        assert_eq!(world.size(), UVec2::ZERO);
+        world.load([1, 1]);
+        world.load([-1, 0]);
+        assert_eq!(world.size(), UVec2::new(8, 6));
+        assert_eq!(total(&world), 101 * 12);
+
+        // Unloaded chunks and negative points are out of bounds.
+        assert!(world.in_bounds([4, 3]));
+        assert!(!world.in_bounds([0, 0]));
+        assert!(!world.in_bounds([-1, 0]));
+        assert_eq!(world.get([-1, 0]), Some(&-100));
+        assert_eq!(world.resolve([-1, 0]), None);
+
+        world.fill(5);
+        assert_eq!(world.count_neighbors([4, 3], 5), 3);
+        assert_eq!(world.view([3, 2], [3, 3]).get([1, 1]), Some(&5));
+    }
+
+    #[test]
+    fn eviction() {
+        let mut world = world().with_eviction(Eviction::LeastRecentlyUsed(2));
+        world.load([0, 0]);
+        world.load([1, 0]);
+        world.mark_clean([0, 0]);
+        world.mark_clean([1, 0]);
+        assert!(world.get([0, 0]).is_some());
+        world.load([2, 0]);
+        assert_eq!(world.chunk_count(), 2);
+        assert!(world.is_loaded([0, 0]), "it was read more recently");
+        assert!(!world.is_loaded([1, 0]));
+
+        world.retain(|coord, _| coord.x == 2);
+        assert_eq!(world.chunk_count(), 1);
+        let world = world.with_eviction(Eviction::Never);
+        assert_eq!(world.eviction(), Eviction::Never);
+    }
+
+    #[test]
+    fn dirty_chunks() {
+        let saved = Rc::new(RefCell::new(Vec::new()));
+        let on_evict = {
+            let saved = saved.clone();
+            move |coord, grid: Grid<i32>| saved.borrow_mut().push((coord, grid[[0, 0]]))
+        };
+        let mut world =
+            world().with_eviction(Eviction::LeastRecentlyUsed(1)).with_on_evict(on_evict);
+
+        world.set([0, 0], 7);
+        assert!(world.is_dirty([0, 0]));
+        world.set([4, 0], 8);
+        world.mark_clean([1, 0]);
+        assert!(!world.is_dirty([1, 0]));
+
+        // Only the chunk that was still dirty is handed over.
+        world.load([2, 0]);
+        assert_eq!(*saved.borrow(), [(IVec2::new(0, 0), 7)]);
+    }
+
+    #[test]
+    fn dirty_chunks_without_callback() {
+        let mut world = world().with_eviction(Eviction::LeastRecentlyUsed(1));
+        world.set([0, 0], 7);
+        world.load([1, 0]);
+        world.mark_clean([1, 0]);
+
+        // The edited chunk has nowhere to be saved, so it stays loaded.
+        world.set([8, 0], 9);
+        assert_eq!(world.chunk_count(), 2);
+        assert!(!world.is_loaded([1, 0]));
+        assert_eq!(world.get([0, 0]), Some(&7));
+
+        world.mark_clean([0, 0]);
+        world.load([3, 0]);
+        assert!(!world.is_loaded([0, 0]));
+    }
+}
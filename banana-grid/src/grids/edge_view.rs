@@ -0,0 +1,103 @@
+use crate::prelude::*;
+use std::marker::PhantomData;
+
+/// A borrowed grid read with an [`EdgeMode`], so points past its edge wrap around, clamp
+/// to it or read a constant.
+///
+/// The view keeps the grid's size and coordinates. It can be handed to anything that
+/// takes a [`GridRead`], such as neighborhood queries or field of view.
+#[derive(Debug)]
+pub struct EdgeView<'a, T, G = Grid<T>> {
+    grid: &'a G,
+    mode: EdgeMode<T>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Clone, G> Clone for EdgeView<'_, T, G> {
+    fn clone(&self) -> Self {
+        Self { grid: self.grid, mode: self.mode.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, T, G: GridRead<T>> EdgeView<'a, T, G> {
+    pub fn new(grid: &'a G, mode: EdgeMode<T>) -> Self {
+        Self { grid, mode, _marker: PhantomData }
+    }
+
+    #[inline]
+    pub fn mode(&self) -> &EdgeMode<T> {
+        &self.mode
+    }
+
+    #[inline]
+    pub fn grid(&self) -> &'a G {
+        self.grid
+    }
+}
+
+impl<T, G: GridRead<T>> GridRead<T> for EdgeView<'_, T, G> {
+    #[inline]
+    fn size(&self) -> UVec2 {
+        self.grid.size()
+    }
+
+    /// The cell that a point reads through the edge mode.
+    #[inline]
+    fn get<P>(&self, point: P) -> Option<&T>
+    where
+        P: GridPoint,
+    {
+        match (self.mode.resolve(point, self.grid.size()), &self.mode) {
+            (Some(point), _) => self.grid.get(point),
+            (None, EdgeMode::Constant(value)) => Some(value),
+            (None, _) => None,
+        }
+    }
+
+    /// Wrapped and clamped points resolve to the cell they read, and points past the edge
+    /// of a [`Constant`](EdgeMode::Constant) view to themselves.
+    fn resolve<P>(&self, point: P) -> Option<IVec2>
+    where
+        P: GridPoint,
+    {
+        match &self.mode {
+            EdgeMode::Constant(_) => Some(point.as_ivec2()),
+            mode => mode.resolve(point, self.grid.size()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::new_fn([4, 3], |p| (p.y * 10 + p.x) as u32)
+    }
+
+    #[test]
+    fn get() {
+        let grid = grid();
+        assert_eq!(grid.with_edges(EdgeMode::Bounded).get([-1, 0]), None);
+        assert_eq!(grid.with_edges(EdgeMode::WrapX).get([-1, 1]), Some(&13));
+        assert_eq!(grid.with_edges(EdgeMode::WrapX).get([1, -1]), None);
+        assert_eq!(grid.with_edges(EdgeMode::Wrap).get([4, -1]), Some(&20));
+        assert_eq!(grid.with_edges(EdgeMode::Clamp).get([9, 9]), Some(&23));
+        assert_eq!(grid.with_edges(EdgeMode::Constant(7)).get([9, 9]), Some(&7));
+        assert_eq!(grid.with_edges(EdgeMode::Constant(7)).get([2, 1]), Some(&12));
+    }
+
+    #[test]
+    fn neighbors() {
+        let walls = Grid::new([3, 3], false);
+        assert_eq!(walls.count_neighbors([0, 0], true), 0);
+        assert_eq!(walls.with_edges(EdgeMode::Constant(true)).count_neighbors([0, 0], true), 5);
+        assert_eq!(walls.with_edges(EdgeMode::WrapX).count_neighbors([0, 0], false), 5);
+        assert_eq!(walls.with_edges(EdgeMode::Wrap).count_neighbors([0, 0], false), 8);
+
+        let wrapped = grid();
+        let wrapped = wrapped.with_edges(EdgeMode::WrapX);
+        assert_eq!(wrapped.resolve([-1, 0]), Some(IVec2::new(3, 0)));
+        assert_eq!(wrapped.resolve([0, -1]), None);
+    }
+}
@@ -20,26 +20,80 @@ pub struct Grid<T> {
     pub cells: Vec<T>,
 }
 
-impl<T: Copy + Clone> GridLike<T> for Grid<T> {
-    ///////////////////////////////////////////////////////////////////////////
-    // New Functionality
-    ///////////////////////////////////////////////////////////////////////////
-    #[inline(always)]
-    fn new<S>(size: S, default_value: T) -> Self
+impl<T> GridRead<T> for Grid<T> {
+    #[inline]
+    fn width(&self) -> u32 {
+        self.size.width()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.size.height()
+    }
+
+    #[inline]
+    fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn get<I>(&self, index: I) -> Option<&T>
     where
-        S: Size2d,
-        Self: Sized,
+        I: GridPoint,
     {
-        Self { size: size.as_uvec2(), cells: vec![default_value; size.count()] }
+        self.try_idx(index).map(|idx| &self.cells[idx])
+    }
+
+    fn get_checked<I>(&self, index: I) -> &T
+    where
+        I: GridPoint,
+    {
+        self.cells.index(self.get_idx(index))
+    }
+}
+
+impl<T> GridWrite<T> for Grid<T> {
+    fn get_mut<I>(&mut self, index: I) -> Option<&mut T>
+    where
+        I: GridPoint,
+    {
+        self.try_idx(index).map(move |idx| &mut self.cells[idx])
+    }
+
+    fn get_mut_checked<I>(&mut self, index: I) -> &mut T
+    where
+        I: GridPoint,
+    {
+        let idx = self.get_idx(index);
+        self.cells.index_mut(idx)
     }
 
-    fn new_grid_map<G, U, F>(grid: G, f: F) -> Self
+    #[inline]
+    fn fill(&mut self, value: T)
     where
-        G: GridLike<U>,
-        F: FnMut(&U) -> T,
-        Self: Sized,
+        T: Clone,
     {
-        Self { cells: grid.data().iter().map(f).collect(), size: grid.size() }
+        self.cells.fill(value);
+    }
+}
+
+impl<T> GridNew<T> for Grid<T> {
+    #[inline(always)]
+    fn new<S>(size: S, default_value: T) -> Self
+    where
+        S: Size2d,
+        T: Clone,
+    {
+        Self { size: size.as_uvec2(), cells: vec![default_value; size.count()] }
     }
 
     #[inline(always)]
@@ -47,7 +101,6 @@ impl<T: Copy + Clone> GridLike<T> for Grid<T> {
     where
         S: Size2d,
         F: FnMut(IVec2) -> T,
-        Self: Sized,
     {
         let mut cells = Vec::with_capacity(size.count());
         for coord in size.iter() {
@@ -92,93 +145,14 @@ impl<T: Copy + Clone> GridLike<T> for Grid<T> {
         cells.resize_with(count, T::default);
         Self { cells, size: size.as_uvec2() }
     }
+}
 
-    ///////////////////////////////////////////////////////////////////////////
-
+impl<T> Grid<T> {
+    /// The cells in row-major order.
     #[inline]
-    fn data(&self) -> &[T] {
+    pub fn data(&self) -> &[T] {
         &self.cells
     }
-
-    #[inline]
-    fn fill(&mut self, value: T)
-    where
-        T: Clone,
-    {
-        self.cells.fill(value);
-    }
-
-    #[inline]
-    fn width(&self) -> u32 {
-        self.size.width()
-    }
-
-    #[inline]
-    fn height(&self) -> u32 {
-        self.size.height()
-    }
-
-    #[inline]
-    fn size(&self) -> UVec2 {
-        self.size
-    }
-
-    #[inline]
-    fn len(&self) -> usize {
-        self.cells.len()
-    }
-
-    #[inline]
-    fn is_empty(&self) -> bool {
-        self.cells.is_empty()
-    }
-
-    fn get<I>(&self, index: I) -> Option<&T>
-    where
-        I: GridPoint,
-    {
-        self.try_idx(index).map(|idx| &self.cells[idx])
-    }
-
-    fn get_mut<I>(&mut self, index: I) -> Option<&mut T>
-    where
-        I: GridPoint,
-    {
-        self.try_idx(index).map(move |idx| &mut self.cells[idx])
-    }
-
-    fn get_checked<I>(&self, index: I) -> &T
-    where
-        I: GridPoint,
-    {
-        self.cells.index(self.get_idx(index))
-    }
-
-    fn get_mut_checked<I>(&mut self, index: I) -> &mut T
-    where
-        I: GridPoint,
-    {
-        self.cells.index_mut(self.get_idx(index))
-    }
-
-    fn count_neighbors<I>(&self, index: I, val: T) -> usize
-    where
-        I: GridPoint,
-        T: std::cmp::PartialEq,
-    {
-        let mut neighbors = 0;
-        for iy in -1..=1 {
-            for ix in -1..=1 {
-                if !(ix == 0 && iy == 0)
-                    && self[((index.x() + ix) as usize, (index.y() + iy) as usize)] == val
-                {
-                    neighbors += 1;
-                }
-            }
-        }
-
-        neighbors
-    }
 }
 
 ///////////////////////////////////////////////////////////////////////////
@@ -187,38 +161,38 @@ impl<T: Copy + Clone> GridLike<T> for Grid<T> {
 
 impl<T: Copy> Grid<T> {
     pub fn map<U: Copy, F: FnMut(&T) -> U>(self, f: F) -> Grid<U> {
-        Grid::new_grid_map(self, f)
+        Grid { size: self.size, cells: self.cells.iter().map(f).collect() }
     }
 
     /// An iterator over all elements in the grid.
     #[inline]
-    pub fn iter(&self) -> GridIter<T> {
+    pub fn iter(&self) -> GridIter<'_, T> {
         self.cells.iter()
     }
 
     /// A mutable iterator over all elements in the grid.
     #[inline]
-    pub fn iter_mut(&mut self) -> GridIterMut<T> {
+    pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
         self.cells.iter_mut()
     }
 
     #[inline]
-    pub fn rows(&self) -> GridRows<T> {
+    pub fn rows(&self) -> GridRows<'_, T> {
         self.cells.chunks(self.size.width() as usize)
     }
 
     #[inline]
-    pub fn rows_mut(&mut self) -> GridRowsMut<T> {
+    pub fn rows_mut(&mut self) -> GridRowsMut<'_, T> {
         self.cells.chunks_mut(self.size.width() as usize)
     }
 
     #[inline]
-    pub fn cols(&self) -> GridRows<T> {
+    pub fn cols(&self) -> GridRows<'_, T> {
         self.cells.chunks(self.size.width() as usize)
     }
 
     #[inline]
-    pub fn cols_mut(&mut self) -> GridRowsMut<T> {
+    pub fn cols_mut(&mut self) -> GridRowsMut<'_, T> {
         self.cells.chunks_mut(self.size.width() as usize)
     }
 
@@ -256,7 +230,7 @@ impl<T: Copy> Grid<T> {
     #[inline]
     pub fn iter_column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
         let w = self.width() as usize;
-        return self.cells[x..].iter().step_by(w);
+        self.cells[x..].iter().step_by(w)
     }
 
     /// A mutable iterator over a single column of the grid.
@@ -265,7 +239,7 @@ impl<T: Copy> Grid<T> {
     #[inline]
     pub fn iter_column_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut T> {
         let w = self.width() as usize;
-        return self.cells[x..].iter_mut().step_by(w);
+        self.cells[x..].iter_mut().step_by(w)
     }
 }
 
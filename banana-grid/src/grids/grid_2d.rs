@@ -15,100 +15,7 @@ pub struct Grid2D<T> {
     data: ndarray::Array2<T>,
 }
 
-impl<T: Copy> GridLike<T> for Grid2D<T> {
-    ///////////////////////////////////////////////////////////////////////////
-    // New Functionality
-    ///////////////////////////////////////////////////////////////////////////
-
-    #[inline(always)]
-    fn new<S>(size: S, default_value: T) -> Self
-    where
-        S: Size2d,
-        Self: Sized,
-    {
-        Self {
-            size: size.as_uvec2(),
-            data: ndarray::Array2::from_elem(size.as_uarray(), default_value),
-        }
-    }
-
-    #[inline(always)]
-    fn new_grid_map<G, U, F>(grid: G, f: F) -> Self
-    where
-        U: Clone,
-        Self: Sized,
-        G: GridLike<U>,
-        F: FnMut(&U) -> T,
-    {
-        Self {
-            size: grid.size(),
-            data: Array2::from_shape_vec(
-                Size2d::as_uarray(&grid.size()),
-                grid.data().iter().map(f).collect(),
-            )
-            .unwrap(),
-        }
-    }
-
-    #[inline(always)]
-    fn new_fn<S, F>(size: S, mut f: F) -> Self
-    where
-        Self: Sized,
-        S: Size2d,
-        F: FnMut(IVec2) -> T,
-    {
-        Self {
-            data: ndarray::Array2::from_shape_fn(size.as_uarray(), |a| f(Size2d::as_ivec2(&a))),
-            size: size.as_uvec2(),
-        }
-    }
-
-    #[inline(always)]
-    fn new_default<S>(size: S) -> Self
-    where
-        T: Default,
-        S: Size2d,
-        Self: Sized,
-    {
-        Self {
-            size: size.as_uvec2(),
-            data: ndarray::Array2::from_elem(size.as_uarray(), T::default()),
-        }
-    }
-
-    #[inline(always)]
-    fn new_clone<S>(size: S, value: T) -> Self
-    where
-        T: Clone,
-        S: Size2d,
-    {
-        Self { size: size.as_uvec2(), data: ndarray::Array2::from_elem(size.as_uarray(), value) }
-    }
-
-    #[inline(always)]
-    fn new_copy<S>(size: S, value: T) -> Self
-    where
-        T: Copy,
-        S: Size2d,
-    {
-        Self { size: size.as_uvec2(), data: ndarray::Array2::from_elem(size.as_uarray(), value) }
-    }
-
-    ///////////////////////////////////////////////////////////////////////////
-
-    #[inline]
-    fn data(&self) -> &[T] {
-        self.data.as_slice().unwrap()
-    }
-
-    #[inline]
-    fn fill(&mut self, value: T)
-    where
-        T: Clone,
-    {
-        self.data.fill(value);
-    }
-
+impl<T> GridRead<T> for Grid2D<T> {
     #[inline]
     fn width(&self) -> u32 {
         self.size.width()
@@ -136,7 +43,7 @@ impl<T: Copy> GridLike<T> for Grid2D<T> {
     }
 
     ///////////////////////////////////////////////////////////////////////////
-    ///  Getters
+    //  Getters
     ///////////////////////////////////////////////////////////////////////////
 
     fn get<I>(&self, index: I) -> Option<&T>
@@ -146,18 +53,20 @@ impl<T: Copy> GridLike<T> for Grid2D<T> {
         self.data.get(index.as_uarray())
     }
 
-    fn get_mut<I>(&mut self, index: I) -> Option<&mut T>
+    fn get_checked<I>(&self, index: I) -> &T
     where
         I: GridPoint,
     {
-        self.data.get_mut(index.as_uarray())
+        self.data.index(index.as_uarray())
     }
+}
 
-    fn get_checked<I>(&self, index: I) -> &T
+impl<T> GridWrite<T> for Grid2D<T> {
+    fn get_mut<I>(&mut self, index: I) -> Option<&mut T>
     where
         I: GridPoint,
     {
-        self.data.index(index.as_uarray())
+        self.data.get_mut(index.as_uarray())
     }
 
     fn get_mut_checked<I>(&mut self, index: I) -> &mut T
@@ -167,26 +76,76 @@ impl<T: Copy> GridLike<T> for Grid2D<T> {
         self.data.index_mut(index.as_uarray())
     }
 
-    ///////////////////////////////////////////////////////////////////////////
-    /// Iters
-    ///////////////////////////////////////////////////////////////////////////
+    #[inline]
+    fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.data.fill(value);
+    }
+}
 
-    fn count_neighbors<I>(&self, index: I, val: T) -> usize
+impl<T> GridNew<T> for Grid2D<T> {
+    #[inline(always)]
+    fn new<S>(size: S, default_value: T) -> Self
     where
-        I: GridPoint,
-        T: std::cmp::PartialEq,
+        S: Size2d,
+        T: Clone,
+    {
+        Self {
+            size: size.as_uvec2(),
+            data: ndarray::Array2::from_elem(size.as_uarray(), default_value),
+        }
+    }
+
+    #[inline(always)]
+    fn new_fn<S, F>(size: S, mut f: F) -> Self
+    where
+        S: Size2d,
+        F: FnMut(IVec2) -> T,
+    {
+        Self {
+            data: ndarray::Array2::from_shape_fn(size.as_uarray(), |a| f(Size2d::as_ivec2(&a))),
+            size: size.as_uvec2(),
+        }
+    }
+
+    #[inline(always)]
+    fn new_default<S>(size: S) -> Self
+    where
+        T: Default,
+        S: Size2d,
     {
-        let mut neighbors = 0;
-        for iy in -1..=1 {
-            for ix in -1..=1 {
-                let x = (index.x() + ix) as usize;
-                let y = (index.y() + iy) as usize;
-                if !(ix == 0 && iy == 0) && self.data[[x, y]] == val {
-                    neighbors += 1;
-                }
-            }
+        Self {
+            size: size.as_uvec2(),
+            data: ndarray::Array2::from_shape_simple_fn(size.as_uarray(), T::default),
         }
-        neighbors
+    }
+
+    #[inline(always)]
+    fn new_clone<S>(size: S, value: T) -> Self
+    where
+        T: Clone,
+        S: Size2d,
+    {
+        Self { size: size.as_uvec2(), data: ndarray::Array2::from_elem(size.as_uarray(), value) }
+    }
+
+    #[inline(always)]
+    fn new_copy<S>(size: S, value: T) -> Self
+    where
+        T: Copy,
+        S: Size2d,
+    {
+        Self { size: size.as_uvec2(), data: ndarray::Array2::from_elem(size.as_uarray(), value) }
+    }
+}
+
+impl<T> Grid2D<T> {
+    /// The cells in the array's memory order, which is column-major.
+    #[inline]
+    pub fn data(&self) -> &[T] {
+        self.data.as_slice().unwrap()
     }
 }
 
@@ -206,7 +165,7 @@ impl<T> Grid2D<T> {
 
     pub fn new_grid_map_ref<U, F>(grid: &Grid2D<U>, f: F) -> Self
     where
-        F: for<'a> FnMut(&U) -> T,
+        F: FnMut(&U) -> T,
     {
         Self { data: grid.data.map(f), size: grid.size }
     }
@@ -217,19 +176,19 @@ impl<T> Grid2D<T> {
 ///////////////////////////////////////////////////////////////////////////
 
 impl<T: Copy> Grid2D<T> {
-    pub fn rows(&self) -> AxisIter<T, Ix1> {
+    pub fn rows(&self) -> AxisIter<'_, T, Ix1> {
         self.data.axis_iter(Axis(0))
     }
 
-    pub fn rows_mut(&mut self) -> AxisIterMut<T, Ix1> {
+    pub fn rows_mut(&mut self) -> AxisIterMut<'_, T, Ix1> {
         self.data.axis_iter_mut(Axis(0))
     }
 
-    pub fn cols(&self) -> AxisIter<T, Ix1> {
+    pub fn cols(&self) -> AxisIter<'_, T, Ix1> {
         self.data.axis_iter(Axis(1))
     }
 
-    pub fn cols_mut(&mut self) -> AxisIterMut<T, Ix1> {
+    pub fn cols_mut(&mut self) -> AxisIterMut<'_, T, Ix1> {
         self.data.axis_iter_mut(Axis(1))
     }
 
@@ -299,18 +258,19 @@ impl<T: Copy> Grid2D<T> {
         self.data.map_inplace(f);
     }
 
-    pub fn slice<I>(&self, start: I, end: I) -> ArrayView<T, Ix2>
+    /// A view of the cells from `start` up to but excluding `end`, indexed `[x, y]`.
+    pub fn slice<I>(&self, start: I, end: I) -> ArrayView<'_, T, Ix2>
     where
         I: GridPoint,
     {
-        self.data.slice(s![start.x()..start.y(), end.x()..end.y()])
+        self.data.slice(s![start.x()..end.x(), start.y()..end.y()])
     }
 
-    pub fn row<X: TryInto<i32>>(&self, x: X) -> ArrayView<T, Ix1> {
+    pub fn row<X: TryInto<i32>>(&self, x: X) -> ArrayView<'_, T, Ix1> {
         self.data.row(x.try_into().ok().expect("Failed to convert x to row_i32") as usize)
     }
 
-    pub fn column<X: TryInto<i32>>(&self, y: X) -> ArrayView<T, Ix1> {
+    pub fn column<X: TryInto<i32>>(&self, y: X) -> ArrayView<'_, T, Ix1> {
         self.data.column(y.try_into().ok().expect("Failed to convert y to column_i32") as usize)
     }
 }
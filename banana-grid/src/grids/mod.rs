@@ -1,2 +1,5 @@
+pub mod chunked;
+pub mod edge_view;
 pub mod grid;
 pub mod grid_2d;
+pub mod view;
@@ -0,0 +1,273 @@
+use crate::prelude::*;
+use std::{
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+/// Clamps a window to a grid of `bounds`, returning its corner and size.
+fn window(bounds: UVec2, corner: IVec2, size: UVec2) -> (IVec2, UVec2) {
+    let min = corner.clamp(IVec2::ZERO, bounds.as_ivec2());
+    let max = (corner + size.as_ivec2()).clamp(min, bounds.as_ivec2());
+    (min, (max - min).as_uvec2())
+}
+
+/// A borrowed rectangular window into a grid, addressed in coordinates local to the
+/// window, with `(0, 0)` at its top left corner.
+#[derive(Debug)]
+pub struct GridView<'a, T, G = Grid<T>> {
+    grid: &'a G,
+    corner: IVec2,
+    size: UVec2,
+    _marker: PhantomData<T>,
+}
+
+// Derived impls would require `T: Clone` and `G: Clone`.
+impl<T, G> Clone for GridView<'_, T, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, G> Copy for GridView<'_, T, G> {}
+
+impl<'a, T, G: GridRead<T>> GridView<'a, T, G> {
+    /// View the part of `grid` with its top left corner at `corner` and of `size`,
+    /// trimmed to the grid where it sticks out.
+    pub fn new(grid: &'a G, corner: impl GridPoint, size: impl Size2d) -> Self {
+        let (corner, size) = window(grid.size(), corner.as_ivec2(), size.as_uvec2());
+        Self { grid, corner, size, _marker: PhantomData }
+    }
+
+    /// Where the window's top left corner is in the underlying grid.
+    #[inline]
+    pub fn corner(&self) -> IVec2 {
+        self.corner
+    }
+
+    /// The point in the underlying grid that a local point refers to.
+    #[inline]
+    pub fn to_grid_point(&self, point: impl GridPoint) -> IVec2 {
+        self.corner + point.as_ivec2()
+    }
+
+    /// The cell at a local point, or `None` outside the window.
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<&'a T> {
+        if self.in_bounds(point) {
+            self.grid.get(self.to_grid_point(point))
+        } else {
+            None
+        }
+    }
+
+    /// A smaller window inside this one, in local coordinates.
+    pub fn view(&self, corner: impl GridPoint, size: impl Size2d) -> Self {
+        let (corner, size) = window(self.size, corner.as_ivec2(), size.as_uvec2());
+        Self { grid: self.grid, corner: self.corner + corner, size, _marker: PhantomData }
+    }
+
+    /// Iterate over every cell of the window in row-major order, with its local point.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        let grid = self.grid;
+        let corner = self.corner;
+        self.size.iter().map(move |p| (p, grid.get_checked(corner + p)))
+    }
+
+    /// Copy the window into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Copy,
+    {
+        Grid::new_fn(self.size, |p| *self.grid.get_checked(self.corner + p))
+    }
+}
+
+impl<T, G: GridRead<T>> GridRead<T> for GridView<'_, T, G> {
+    #[inline]
+    fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    #[inline]
+    fn get<P>(&self, point: P) -> Option<&T>
+    where
+        P: GridPoint,
+    {
+        GridView::get(self, point)
+    }
+}
+
+impl<T, G: GridRead<T>, P: GridPoint> Index<P> for GridView<'_, T, G> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, point: P) -> &T {
+        assert!(self.in_bounds(point), "{} is outside a view of {}", point.as_ivec2(), self.size);
+        self.grid.get_checked(self.to_grid_point(point))
+    }
+}
+
+/// A mutably borrowed rectangular window into a grid, addressed in coordinates local to
+/// the window, with `(0, 0)` at its top left corner.
+#[derive(Debug)]
+pub struct GridViewMut<'a, T, G = Grid<T>> {
+    grid: &'a mut G,
+    corner: IVec2,
+    size: UVec2,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T, G: GridWrite<T>> GridViewMut<'a, T, G> {
+    /// View the part of `grid` with its top left corner at `corner` and of `size`,
+    /// trimmed to the grid where it sticks out.
+    pub fn new(grid: &'a mut G, corner: impl GridPoint, size: impl Size2d) -> Self {
+        let (corner, size) = window(grid.size(), corner.as_ivec2(), size.as_uvec2());
+        Self { grid, corner, size, _marker: PhantomData }
+    }
+
+    /// A read-only view of the same window.
+    #[inline]
+    pub fn as_view(&self) -> GridView<'_, T, G> {
+        GridView { grid: self.grid, corner: self.corner, size: self.size, _marker: PhantomData }
+    }
+
+    /// Where the window's top left corner is in the underlying grid.
+    #[inline]
+    pub fn corner(&self) -> IVec2 {
+        self.corner
+    }
+
+    /// The point in the underlying grid that a local point refers to.
+    #[inline]
+    pub fn to_grid_point(&self, point: impl GridPoint) -> IVec2 {
+        self.corner + point.as_ivec2()
+    }
+
+    /// A smaller window inside this one, in local coordinates.
+    pub fn view_mut(&mut self, corner: impl GridPoint, size: impl Size2d) -> GridViewMut<'_, T, G> {
+        let (corner, size) = window(self.size, corner.as_ivec2(), size.as_uvec2());
+        GridViewMut { grid: self.grid, corner: self.corner + corner, size, _marker: PhantomData }
+    }
+
+    /// Copy the window into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Copy,
+    {
+        self.as_view().to_grid()
+    }
+}
+
+impl<T, G: GridWrite<T>> GridRead<T> for GridViewMut<'_, T, G> {
+    #[inline]
+    fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// The cell at a local point, or `None` outside the window.
+    #[inline]
+    fn get<P>(&self, point: P) -> Option<&T>
+    where
+        P: GridPoint,
+    {
+        if self.in_bounds(point) {
+            self.grid.get(self.to_grid_point(point))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, G: GridWrite<T>> GridWrite<T> for GridViewMut<'_, T, G> {
+    /// The cell at a local point, or `None` outside the window.
+    #[inline]
+    fn get_mut<P>(&mut self, point: P) -> Option<&mut T>
+    where
+        P: GridPoint,
+    {
+        if self.in_bounds(point) {
+            self.grid.get_mut(self.to_grid_point(point))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, G: GridWrite<T>, P: GridPoint> Index<P> for GridViewMut<'_, T, G> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, point: P) -> &T {
+        assert!(self.in_bounds(point), "{} is outside a view of {}", point.as_ivec2(), self.size);
+        self.grid.get_checked(self.to_grid_point(point))
+    }
+}
+
+impl<T, G: GridWrite<T>, P: GridPoint> IndexMut<P> for GridViewMut<'_, T, G> {
+    #[inline]
+    fn index_mut(&mut self, point: P) -> &mut T {
+        assert!(self.in_bounds(point), "{} is outside a view of {}", point.as_ivec2(), self.size);
+        self.grid.get_mut_checked(self.to_grid_point(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::new_fn([6, 4], |p| (p.y * 10 + p.x) as u32)
+    }
+
+    #[test]
+    fn local_coordinates() {
+        let grid = grid();
+        let view = grid.view([2, 1], [3, 2]);
+        assert_eq!(view.size(), UVec2::new(3, 2));
+        assert_eq!(view[[0, 0]], 12);
+        assert_eq!(view.get([2, 1]), Some(&24));
+        assert_eq!(view.get([3, 0]), None);
+        assert_eq!(view.get([-1, 0]), None);
+        assert_eq!(view.to_grid().cells, [12, 13, 14, 22, 23, 24]);
+        assert_eq!(view.iter().map(|(_, v)| *v).collect::<Vec<_>>(), view.to_grid().cells);
+
+        let inner = view.view([1, 1], [5, 5]);
+        assert_eq!(inner.corner(), IVec2::new(3, 2));
+        assert_eq!(inner.to_grid().cells, [23, 24]);
+    }
+
+    #[test]
+    fn trimmed() {
+        let grid = grid();
+        let view = grid.view([-2, 2], [4, 4]);
+        assert_eq!(view.corner(), IVec2::new(0, 2));
+        assert_eq!(view.size(), UVec2::new(2, 2));
+        assert!(grid.view([7, 0], [2, 2]).is_empty());
+    }
+
+    #[test]
+    fn write() {
+        let mut grid = grid();
+        let mut view = grid.view_mut([1, 1], [2, 2]);
+        view[[0, 0]] = 0;
+        *view.get_mut([1, 1]).unwrap() = 1;
+        assert!(view.get_mut([2, 0]).is_none());
+        view.view_mut([1, 0], [1, 1]).fill(7);
+        assert_eq!(view.as_view().to_grid().cells, [0, 7, 21, 1]);
+        assert_eq!(grid.rows().nth(1).unwrap(), [10, 0, 7, 13, 14, 15]);
+        assert_eq!(grid.rows().nth(2).unwrap(), [20, 21, 1, 23, 24, 25]);
+    }
+
+    #[test]
+    fn grid_2d() {
+        let grid = grid();
+        let grid_2d = Grid2D::new_fn([6, 4], |p| (p.y * 10 + p.x) as u32);
+        assert_eq!(grid_2d.view([2, 1], [3, 2]).to_grid(), grid.view([2, 1], [3, 2]).to_grid());
+
+        // `slice` takes the corners of the window, the end excluded.
+        let slice = grid_2d.slice(IVec2::new(2, 1), IVec2::new(5, 3));
+        assert_eq!(slice.shape(), [3, 2]);
+        assert_eq!(slice[[0, 0]], 12);
+        assert_eq!(slice[[2, 1]], 24);
+    }
+}
@@ -1,4 +1,6 @@
 mod axis;
+mod convolve;
+mod edge_mode;
 mod grid_like;
 mod grid_point;
 mod grids;
@@ -6,10 +8,15 @@ mod size_2d;
 
 pub mod prelude {
     pub use crate::axis::*;
+    pub use crate::convolve::*;
+    pub use crate::edge_mode::*;
     pub use crate::grid_like::*;
     pub use crate::grid_point::*;
+    pub use crate::grids::chunked::*;
+    pub use crate::grids::edge_view::*;
     pub use crate::grids::grid::*;
     pub use crate::grids::grid_2d::*;
+    pub use crate::grids::view::*;
     pub use crate::size_2d::*;
     pub use crate::{impl_grid_point_array, impl_grid_point_tuple};
 
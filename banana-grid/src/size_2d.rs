@@ -36,7 +36,7 @@ impl Iterator for PointIterRowMajor {
 
 ////////////////////////////////////////////////////////////
 
-pub const MAX_SIZE_FIELD: u32 = ::core::i32::MAX as u32;
+pub const MAX_SIZE_FIELD: u32 = i32::MAX as u32;
 pub const MAX_SIZE: UVec2 = UVec2 { x: MAX_SIZE_FIELD, y: MAX_SIZE_FIELD };
 
 #[derive(Debug)]
@@ -59,7 +59,7 @@ pub trait Size2d: Clone + Copy {
     }
 
     /// Creates a new `UVec2`.
-    /// Panics if `width` or `width` is greater than `::core::i32::MAX as u32`
+    /// Panics if `width` or `width` is greater than `i32::MAX as u32`
     #[allow(clippy::new_ret_no_self)]
     fn new(width: u32, height: u32) -> UVec2 {
         match Self::try_new(width, height) {
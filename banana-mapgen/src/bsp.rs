@@ -0,0 +1,214 @@
+use crate::prelude::*;
+use banana_geometry::prelude::Rect;
+use banana_grid::prelude::*;
+use rand::Rng;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Rooms-and-corridors maps made by recursively splitting the map in two.
+///
+/// Every leaf of the split gets one room, and the two halves of every split are joined
+/// by an L-shaped corridor between one room from each, so every room is reachable.
+///
+/// See: [Basic BSP Dungeon generation](http://www.roguebasin.com/index.php/Basic_BSP_Dungeon_generation)
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BspGenerator {
+    /// The size of the generated map.
+    pub size: UVec2,
+    /// Partitions are never split into pieces narrower or shorter than this.
+    pub min_leaf_size: u32,
+    /// How many times the map may be split in a row, at most.
+    pub max_depth: u32,
+    /// The smallest width and height of a room, walls not included.
+    pub min_room_size: u32,
+}
+
+/// A map made by a [`BspGenerator`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BspMap {
+    pub grid: Grid<Tile>,
+    /// Every room carved into the map, in the order the leaves were visited.
+    pub rooms: Vec<Rect>,
+}
+
+impl BspGenerator {
+    pub fn new(size: impl Size2d) -> Self {
+        Self { size: size.as_uvec2(), min_leaf_size: 8, max_depth: 8, min_room_size: 3 }
+    }
+
+    #[must_use]
+    pub fn with_min_leaf_size(mut self, min_leaf_size: u32) -> Self {
+        self.min_leaf_size = min_leaf_size;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    #[must_use]
+    pub fn with_min_room_size(mut self, min_room_size: u32) -> Self {
+        self.min_room_size = min_room_size;
+        self
+    }
+
+    /// Generate a map, drawing every random choice from `rng`.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> BspMap {
+        let mut rooms = Vec::new();
+        let mut corridors = Vec::new();
+        let bounds = Rect::from_corners(IVec2::ZERO, self.size.as_ivec2());
+        self.split(rng, bounds, 0, &mut rooms, &mut corridors);
+
+        let mut grid = Grid::new_fn(self.size, |p| {
+            Tile::from(rooms.iter().any(|room| room_contains(room, p)))
+        });
+        for (from, to, horizontal_first) in corridors {
+            carve_corridor(&mut grid, from, to, horizontal_first);
+        }
+
+        BspMap { grid, rooms }
+    }
+
+    /// Splits `leaf` or carves a room into it, returning the index of a room inside.
+    fn split<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        leaf: Rect,
+        depth: u32,
+        rooms: &mut Vec<Rect>,
+        corridors: &mut Vec<(IVec2, IVec2, bool)>,
+    ) -> Option<usize> {
+        let min = self.min_leaf_size.max(self.min_room_size + 2) as i32;
+        let (width, height) = (leaf.width(), leaf.height());
+        let can_split_x = width >= min * 2;
+        let can_split_y = height >= min * 2;
+
+        if depth >= self.max_depth || !(can_split_x || can_split_y) {
+            return self.carve_room(rng, leaf).map(|room| {
+                rooms.push(room);
+                rooms.len() - 1
+            });
+        }
+
+        // Prefer cutting across the long side so leaves stay roughly square.
+        let split_x = match (can_split_x, can_split_y) {
+            (true, false) => true,
+            (false, true) => false,
+            _ if width * 4 > height * 5 => true,
+            _ if height * 4 > width * 5 => false,
+            _ => rng.gen(),
+        };
+
+        let (first, second) = if split_x {
+            let x = leaf.min.x + rng.gen_range(min..=width - min);
+            (
+                Rect::from_corners(leaf.min, IVec2::new(x, leaf.max.y)),
+                Rect::from_corners(IVec2::new(x, leaf.min.y), leaf.max),
+            )
+        } else {
+            let y = leaf.min.y + rng.gen_range(min..=height - min);
+            (
+                Rect::from_corners(leaf.min, IVec2::new(leaf.max.x, y)),
+                Rect::from_corners(IVec2::new(leaf.min.x, y), leaf.max),
+            )
+        };
+
+        let first = self.split(rng, first, depth + 1, rooms, corridors);
+        let second = self.split(rng, second, depth + 1, rooms, corridors);
+        match (first, second) {
+            (Some(a), Some(b)) => {
+                corridors.push((rooms[a].center(), rooms[b].center(), rng.gen()));
+                Some(if rng.gen() { a } else { b })
+            }
+            (room, None) | (None, room) => room,
+        }
+    }
+
+    /// A random room inside `leaf`, leaving at least a wall on every side.
+    fn carve_room<R: Rng + ?Sized>(&self, rng: &mut R, leaf: Rect) -> Option<Rect> {
+        let min_room = self.min_room_size.max(1) as i32;
+        let (max_width, max_height) = (leaf.width() - 2, leaf.height() - 2);
+        if max_width < min_room || max_height < min_room {
+            return None;
+        }
+
+        let size =
+            IVec2::new(rng.gen_range(min_room..=max_width), rng.gen_range(min_room..=max_height));
+        let offset = IVec2::new(
+            rng.gen_range(1..=leaf.width() - 1 - size.x),
+            rng.gen_range(1..=leaf.height() - 1 - size.y),
+        );
+        let min = leaf.min + offset;
+        Some(Rect::from_corners(min, min + size))
+    }
+}
+
+/// Whether a point lies in the cells covered by a room, its maximum corner excluded.
+fn room_contains(room: &Rect, point: IVec2) -> bool {
+    point.cmpge(room.min).all() && point.cmplt(room.max).all()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::VecDeque;
+
+    fn generate(seed: u64) -> BspMap {
+        BspGenerator::new([80, 50]).generate(&mut StdRng::seed_from_u64(seed))
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7).grid, generate(8).grid);
+        println!("{}", tiles_to_string(&generate(7).grid));
+    }
+
+    #[test]
+    fn rooms() {
+        for seed in 0..20 {
+            let map = generate(seed);
+            assert!(map.rooms.len() >= 4);
+            for (i, room) in map.rooms.iter().enumerate() {
+                assert!(room.min.cmpgt(IVec2::ZERO).all());
+                assert!(room.max.cmplt(IVec2::new(80, 50)).all());
+                assert!(room.width() >= 3 && room.height() >= 3);
+                assert!(room.into_iter().all(|p| map.grid[p].is_floor()));
+                // Rooms keep at least one wall between them.
+                for other in &map.rooms[i + 1..] {
+                    assert!(!room.intersects(*other));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn connected() {
+        for seed in 0..20 {
+            let map = generate(seed);
+            let start = map.rooms[0].center();
+            let mut seen = Grid::new(map.grid.size(), false);
+            let mut queue = VecDeque::from([start]);
+            seen[start] = true;
+            while let Some(p) = queue.pop_front() {
+                for d in [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y] {
+                    let next = p + d;
+                    if map.grid.get(next).is_some_and(|t| t.is_floor()) && !seen[next] {
+                        seen[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            let floors = map.grid.cells.iter().filter(|t| t.is_floor()).count();
+            assert_eq!(seen.cells.iter().filter(|s| **s).count(), floors);
+        }
+    }
+}
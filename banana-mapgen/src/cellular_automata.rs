@@ -0,0 +1,246 @@
+use crate::prelude::*;
+use banana_direction::prelude::Neighborhood;
+use banana_grid::prelude::*;
+use rand::Rng;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Birth and survival rules for a cellular automaton, by count of live neighbors.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct CaveRule {
+    /// A dead cell comes alive with any of these neighbor counts.
+    pub birth: [bool; 9],
+    /// A live cell stays alive with any of these neighbor counts.
+    pub survival: [bool; 9],
+}
+
+impl CaveRule {
+    /// The classic cave rule, B5678/S45678.
+    pub const CAVES: Self = Self::new(&[5, 6, 7, 8], &[4, 5, 6, 7, 8]);
+
+    /// Create a rule from the neighbor counts for birth and for survival. Counts above
+    /// eight are ignored.
+    pub const fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Self { birth: counts(birth), survival: counts(survival) }
+    }
+
+    /// Whether a cell is alive next step, given whether it is alive now.
+    #[inline]
+    pub fn next(&self, alive: bool, neighbors: usize) -> bool {
+        let rule = if alive { &self.survival } else { &self.birth };
+        rule.get(neighbors).copied().unwrap_or_default()
+    }
+}
+
+impl Default for CaveRule {
+    fn default() -> Self {
+        Self::CAVES
+    }
+}
+
+const fn counts(list: &[usize]) -> [bool; 9] {
+    let mut counts = [false; 9];
+    let mut i = 0;
+    while i < list.len() {
+        if list[i] < 9 {
+            counts[list[i]] = true;
+        }
+        i += 1;
+    }
+    counts
+}
+
+/// Caves grown from random noise by a cellular automaton.
+///
+/// Live cells are walls. The edge of the map is always wall, so caves are closed off.
+///
+/// See: [Cellular Automata Method for Generating Random Cave-Like Levels](http://www.roguebasin.com/index.php/Cellular_Automata_Method_for_Generating_Random_Cave-Like_Levels)
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaveGenerator {
+    /// The size of the generated map.
+    pub size: UVec2,
+    /// The chance of each cell starting out as a wall, clamped to `0.0..=1.0`.
+    pub wall_chance: f64,
+    pub rule: CaveRule,
+    /// How many times the rule is applied.
+    pub iterations: u32,
+    /// Whether to fill in every open area but the largest, so the whole cave connects.
+    pub keep_largest_region: bool,
+}
+
+impl CaveGenerator {
+    pub fn new(size: impl Size2d) -> Self {
+        Self {
+            size: size.as_uvec2(),
+            wall_chance: 0.45,
+            rule: CaveRule::CAVES,
+            iterations: 5,
+            keep_largest_region: true,
+        }
+    }
+
+    /// Set the chance of each cell starting out as a wall, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn with_wall_chance(mut self, wall_chance: f64) -> Self {
+        self.wall_chance = clamp_chance(wall_chance);
+        self
+    }
+
+    #[must_use]
+    pub fn with_rule(mut self, rule: CaveRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    #[must_use]
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    #[must_use]
+    pub fn with_keep_largest_region(mut self, keep_largest_region: bool) -> Self {
+        self.keep_largest_region = keep_largest_region;
+        self
+    }
+
+    /// Generate a map where `true` is a wall, drawing the starting noise from `rng`.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Grid<bool> {
+        let size = self.size;
+        let chance = clamp_chance(self.wall_chance);
+        let mut walls = Grid::new_fn(size, |p| is_edge(size, p) || rng.gen_bool(chance));
+        for _ in 0..self.iterations {
+            walls = self.step(&walls);
+        }
+        if self.keep_largest_region {
+            keep_largest_region(&mut walls);
+        }
+        walls
+    }
+
+    /// Apply the rule to every cell once. The edge of the map is left as wall.
+    pub fn step(&self, walls: &Grid<bool>) -> Grid<bool> {
+        let size = walls.size();
+        Grid::new_fn(size, |p| {
+            is_edge(size, p) || self.rule.next(walls[p], walls.count_neighbors(p, true))
+        })
+    }
+}
+
+/// Keeps a chance in `0.0..=1.0`, treating NaN as no chance at all.
+fn clamp_chance(chance: f64) -> f64 {
+    if chance.is_nan() {
+        0.0
+    } else {
+        chance.clamp(0.0, 1.0)
+    }
+}
+
+fn is_edge(size: UVec2, p: IVec2) -> bool {
+    p.x == 0 || p.y == 0 || p.x == size.x as i32 - 1 || p.y == size.y as i32 - 1
+}
+
+/// Fills in every open area that isn't the largest one, by four-way connectivity.
+fn keep_largest_region(walls: &mut Grid<bool>) {
+    let regions = Regions::new(walls, Neighborhood::Cardinal, |wall| !wall);
+    let largest = regions.largest();
+    for (wall, label) in walls.cells.iter_mut().zip(&regions.labels().cells) {
+        if label.is_some() && *label != largest {
+            *wall = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn print(walls: &Grid<bool>) {
+        let tiles = Grid::new_fn(walls.size(), |p| Tile::from(!walls[p]));
+        println!("{}", tiles_to_string(&tiles));
+    }
+
+    #[test]
+    fn rule() {
+        let rule = CaveRule::CAVES;
+        assert!(rule.next(false, 5));
+        assert!(!rule.next(false, 4));
+        assert!(rule.next(true, 4));
+        assert!(!rule.next(true, 3));
+        assert!(!rule.next(true, 9));
+    }
+
+    #[test]
+    fn seeded() {
+        let caves = CaveGenerator::new([60, 30]);
+        let a = caves.generate(&mut StdRng::seed_from_u64(3));
+        assert_eq!(a, caves.generate(&mut StdRng::seed_from_u64(3)));
+        assert_ne!(a, caves.generate(&mut StdRng::seed_from_u64(4)));
+        print(&a);
+    }
+
+    #[test]
+    fn closed_edges() {
+        for seed in 0..10 {
+            let walls = CaveGenerator::new([40, 20])
+                .with_wall_chance(0.3)
+                .with_keep_largest_region(false)
+                .generate(&mut StdRng::seed_from_u64(seed));
+            for p in walls.size().iter() {
+                if p.x == 0 || p.y == 0 || p.x == 39 || p.y == 19 {
+                    assert!(walls[p], "{p}");
+                }
+            }
+        }
+
+        // Without any noise, the rule only grows the corners in from the edge.
+        let walls = CaveGenerator::new([10, 10])
+            .with_wall_chance(0.0)
+            .with_iterations(1)
+            .generate(&mut StdRng::seed_from_u64(0));
+        assert!(walls[IVec2::new(1, 1)]);
+        assert!(!walls[IVec2::new(1, 2)]);
+    }
+
+    #[test]
+    fn wall_chance_out_of_range() {
+        let full = CaveGenerator::new([10, 10]).with_wall_chance(1.5).with_iterations(0);
+        assert_eq!(full.wall_chance, 1.0);
+        assert!(full.generate(&mut StdRng::seed_from_u64(0)).iter().all(|wall| *wall));
+
+        let empty = CaveGenerator::new([10, 10]).with_wall_chance(-0.1).with_iterations(0);
+        assert_eq!(empty.wall_chance, 0.0);
+        assert!(!empty.generate(&mut StdRng::seed_from_u64(0))[IVec2::new(5, 5)]);
+
+        // The field can still be set directly, so it's clamped again when generating.
+        let caves = CaveGenerator { wall_chance: 1.5, ..full };
+        assert!(caves.generate(&mut StdRng::seed_from_u64(0)).iter().all(|wall| *wall));
+    }
+
+    #[test]
+    fn single_region() {
+        for seed in 0..10 {
+            let walls = CaveGenerator::new([50, 40]).generate(&mut StdRng::seed_from_u64(seed));
+            let floors: Vec<IVec2> = walls.size().iter().filter(|p| !walls[*p]).collect();
+            assert!(floors.len() > 200);
+
+            let mut seen = Grid::new(walls.size(), false);
+            let mut stack = vec![floors[0]];
+            seen[floors[0]] = true;
+            while let Some(p) = stack.pop() {
+                for d in [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y] {
+                    if walls.get(p + d) == Some(&false) && !seen[p + d] {
+                        seen[p + d] = true;
+                        stack.push(p + d);
+                    }
+                }
+            }
+            assert!(floors.iter().all(|p| seen[*p]));
+        }
+    }
+}
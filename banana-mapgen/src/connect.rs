@@ -0,0 +1,161 @@
+use crate::prelude::*;
+use banana_direction::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+
+/// Carves corridors between the disconnected regions of a map until they are all
+/// connected, returning the two ends of every corridor carved.
+///
+/// Regions are the connected areas of cells matching `is_floor`. They are linked along a
+/// minimum spanning tree, each link joining the two closest cells of its regions by
+/// `distance`, with an L-shaped corridor of `floor` running along the longer axis first.
+pub fn connect_regions<T, F>(
+    grid: &mut Grid<T>,
+    connectivity: Neighborhood,
+    distance: DistanceAlg,
+    mut is_floor: F,
+    floor: T,
+) -> Vec<(IVec2, IVec2)>
+where
+    T: Copy,
+    F: FnMut(&T) -> bool,
+{
+    let regions = Regions::new(grid, connectivity, &mut is_floor);
+    if regions.len() < 2 {
+        return Vec::new();
+    }
+
+    // The closest cells of two regions are always on their borders.
+    let mut borders = vec![Vec::new(); regions.len()];
+    for point in grid.size().iter() {
+        let Some(label) = regions.label(point) else { continue };
+        if CardinalDirections.into_iter().any(|d| regions.label(point + d.coord()) != Some(label)) {
+            borders[label as usize].push(point);
+        }
+    }
+    let closest = |a: usize, b: usize| {
+        let mut best = (f32::INFINITY, IVec2::ZERO, IVec2::ZERO);
+        for from in &borders[a] {
+            for to in &borders[b] {
+                let d = distance.distance2d(*from, *to);
+                if d < best.0 {
+                    best = (d, *from, *to);
+                }
+            }
+        }
+        best
+    };
+
+    // Prim's algorithm, keeping the cheapest link from the tree to every region not yet
+    // in it. Regions in the tree have no link.
+    let mut links: Vec<Option<(f32, IVec2, IVec2)>> =
+        (0..regions.len()).map(|region| (region != 0).then(|| closest(0, region))).collect();
+    let mut corridors = Vec::new();
+    while let Some(next) = (0..links.len())
+        .filter(|region| links[*region].is_some())
+        .min_by(|a, b| links[*a].unwrap().0.total_cmp(&links[*b].unwrap().0))
+    {
+        let (_, from, to) = links[next].take().unwrap();
+        corridors.push((from, to));
+        for (region, link) in links.iter_mut().enumerate() {
+            if let Some((cost, ..)) = *link {
+                let candidate = closest(next, region);
+                if candidate.0 < cost {
+                    *link = Some(candidate);
+                }
+            }
+        }
+    }
+
+    for (from, to) in &corridors {
+        let delta = (*to - *from).abs();
+        carve_corridor_with(grid, *from, *to, delta.x >= delta.y, floor);
+    }
+    corridors
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::Neighborhood;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn map(text: &str) -> Grid<Tile> {
+        let rows: Vec<&str> = text.trim().lines().map(str::trim).collect();
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| Tile::from(rows[p.y as usize].as_bytes()[p.x as usize] == b'.'))
+    }
+
+    #[test]
+    fn spanning_tree() {
+        let mut grid = map("
+            ##############
+            #..###..####.#
+            #..###..####.#
+            ##############
+            ##############
+            #..###########
+            ##############
+        ");
+        let corridors = connect_regions(
+            &mut grid,
+            Neighborhood::Cardinal,
+            DistanceAlg::Manhattan,
+            |tile| tile.is_floor(),
+            Tile::Floor,
+        );
+
+        // Each region joins the one closest to it, never the farther pairs.
+        assert_eq!(corridors.len(), 3);
+        assert!(corridors.contains(&(IVec2::new(2, 1), IVec2::new(6, 1))));
+        assert!(corridors.contains(&(IVec2::new(7, 1), IVec2::new(12, 1))));
+        assert!(corridors.contains(&(IVec2::new(1, 2), IVec2::new(1, 5))));
+        assert_eq!(Regions::new(&grid, Neighborhood::Cardinal, |tile| tile.is_floor()).len(), 1);
+        println!("{}", tiles_to_string(&grid));
+    }
+
+    #[test]
+    fn already_connected() {
+        let mut grid = map("
+            #####
+            #...#
+            #####
+        ");
+        let before = grid.clone();
+        let corridors = connect_regions(
+            &mut grid,
+            Neighborhood::Cardinal,
+            DistanceAlg::Pythagoras,
+            |tile| tile.is_floor(),
+            Tile::Floor,
+        );
+        assert!(corridors.is_empty());
+        assert_eq!(grid, before);
+    }
+
+    #[cfg(feature = "rng")]
+    #[test]
+    fn caves() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        for seed in 0..10 {
+            let mut walls = CaveGenerator::new([60, 40])
+                .with_keep_largest_region(false)
+                .generate(&mut StdRng::seed_from_u64(seed));
+            let floors = walls.cells.iter().filter(|wall| !**wall).count();
+            let before = Regions::new(&walls, Neighborhood::All, |wall| !wall).len();
+
+            let corridors = connect_regions(
+                &mut walls,
+                Neighborhood::All,
+                DistanceAlg::Pythagoras,
+                |wall| !wall,
+                false,
+            );
+            assert_eq!(corridors.len(), before - 1);
+            assert_eq!(Regions::new(&walls, Neighborhood::All, |wall| !wall).len(), 1);
+            assert!(walls.cells.iter().filter(|wall| !**wall).count() >= floors);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::prelude::*;
+use banana_grid::prelude::*;
+
+/// Carves an L-shaped corridor of floor between two points.
+///
+/// The corridor runs along `from`'s row and then `to`'s column when `horizontal_first`
+/// is set, and along `from`'s column and then `to`'s row otherwise. Cells outside the
+/// grid are skipped.
+pub fn carve_corridor(grid: &mut Grid<Tile>, from: IVec2, to: IVec2, horizontal_first: bool) {
+    carve_corridor_with(grid, from, to, horizontal_first, Tile::Floor);
+}
+
+/// Like [`carve_corridor`], for any grid, setting every cell of the corridor to `value`.
+pub fn carve_corridor_with<T: Copy>(
+    grid: &mut Grid<T>,
+    from: IVec2,
+    to: IVec2,
+    horizontal_first: bool,
+    value: T,
+) {
+    let corner = if horizontal_first { IVec2::new(to.x, from.y) } else { IVec2::new(from.x, to.y) };
+    carve_straight(grid, from, corner, value);
+    carve_straight(grid, corner, to, value);
+}
+
+/// Carves a straight horizontal or vertical line, both ends included.
+fn carve_straight<T: Copy>(grid: &mut Grid<T>, from: IVec2, to: IVec2, value: T) {
+    let step = (to - from).signum();
+    let mut point = from;
+    loop {
+        if let Some(cell) = grid.get_mut(point) {
+            *cell = value;
+        }
+        if point == to {
+            break;
+        }
+        point += step;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn l_shape() {
+        let mut grid = Grid::new([6, 4], Tile::Wall);
+        carve_corridor(&mut grid, IVec2::new(1, 1), IVec2::new(4, 2), true);
+        assert_eq!(tiles_to_string(&grid), "######\n#....#\n####.#\n######");
+
+        let mut grid = Grid::new([6, 4], Tile::Wall);
+        carve_corridor(&mut grid, IVec2::new(1, 1), IVec2::new(4, 2), false);
+        assert_eq!(tiles_to_string(&grid), "######\n#.####\n#....#\n######");
+    }
+}
@@ -0,0 +1,172 @@
+use crate::drunkards_walk::{random_step, Inner};
+use crate::prelude::*;
+use banana_grid::prelude::*;
+use rand::Rng;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Which way the walkers of a [`Dla`] travel.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DlaMode {
+    /// Walkers start anywhere and stick to the floor where they first touch it,
+    /// growing branching, coral-like shapes.
+    #[default]
+    WalkInwards,
+    /// Walkers start in the middle and dig the first wall they stumble into, growing a
+    /// rounder blob.
+    WalkOutwards,
+}
+
+/// Maps grown by diffusion-limited aggregation from a seed in the middle.
+///
+/// See: [Diffusion-limited aggregation](http://www.roguebasin.com/index.php/Diffusion-limited_aggregation)
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dla {
+    /// The size of the generated map.
+    pub size: UVec2,
+    pub mode: DlaMode,
+    /// Stop once this fraction of the map is floor.
+    pub floor_percent: f32,
+    /// Whether walkers may also stumble diagonally.
+    pub diagonal: bool,
+}
+
+impl Dla {
+    pub fn new(size: impl Size2d) -> Self {
+        Self {
+            size: size.as_uvec2(),
+            mode: DlaMode::WalkInwards,
+            floor_percent: 0.25,
+            diagonal: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_mode(mut self, mode: DlaMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    #[must_use]
+    pub fn with_floor_percent(mut self, floor_percent: f32) -> Self {
+        self.floor_percent = floor_percent;
+        self
+    }
+
+    #[must_use]
+    pub fn with_diagonal(mut self, diagonal: bool) -> Self {
+        self.diagonal = diagonal;
+        self
+    }
+
+    /// Generate a map, drawing every step from `rng`.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Grid<Tile> {
+        let mut grid = Grid::new(self.size, Tile::Wall);
+        let Some(inner) = Inner::new(self.size) else { return grid };
+
+        let target = (inner.area() as f32 * self.floor_percent.clamp(0.0, 1.0)).ceil() as usize;
+        let center = inner.center();
+        let mut floors = 0;
+        for point in
+            [center, center + IVec2::X, center + IVec2::Y, center - IVec2::X, center - IVec2::Y]
+        {
+            if inner.contains(point) && floors < target.max(1) {
+                grid[point] = Tile::Floor;
+                floors += 1;
+            }
+        }
+
+        while floors < target {
+            let dug = match self.mode {
+                DlaMode::WalkInwards => self.walk_inwards(rng, &grid, inner),
+                DlaMode::WalkOutwards => self.walk_outwards(rng, &grid, inner, center),
+            };
+            grid[dug] = Tile::Floor;
+            floors += 1;
+        }
+
+        grid
+    }
+
+    /// Walks from a random wall until the next step is onto floor, stopping short of it.
+    fn walk_inwards<R: Rng + ?Sized>(&self, rng: &mut R, grid: &Grid<Tile>, inner: Inner) -> IVec2 {
+        let mut point = inner.random(rng);
+        while grid[point].is_floor() {
+            point = inner.random(rng);
+        }
+
+        loop {
+            let next = inner.clamp(point + random_step(rng, self.diagonal));
+            if grid[next].is_floor() {
+                return point;
+            }
+            point = next;
+        }
+    }
+
+    /// Walks from `start` until stepping onto a wall.
+    fn walk_outwards<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        grid: &Grid<Tile>,
+        inner: Inner,
+        start: IVec2,
+    ) -> IVec2 {
+        let mut point = start;
+        while grid[point].is_floor() {
+            point = inner.clamp(point + random_step(rng, self.diagonal));
+        }
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::VecDeque;
+
+    /// The number of floor tiles connected to `start`, and the total number of floors.
+    fn connected(grid: &Grid<Tile>, start: IVec2) -> (usize, usize) {
+        let mut seen = Grid::new(grid.size(), false);
+        let mut queue = VecDeque::from([start]);
+        seen[start] = true;
+        let mut count = 0;
+        while let Some(p) = queue.pop_front() {
+            count += 1;
+            for d in [IVec2::X, IVec2::Y, IVec2::NEG_X, IVec2::NEG_Y] {
+                if grid.get(p + d).is_some_and(|t| t.is_floor()) && !seen[p + d] {
+                    seen[p + d] = true;
+                    queue.push_back(p + d);
+                }
+            }
+        }
+        (count, grid.cells.iter().filter(|t| t.is_floor()).count())
+    }
+
+    #[test]
+    fn seeded() {
+        for mode in [DlaMode::WalkInwards, DlaMode::WalkOutwards] {
+            let dla = Dla::new([50, 30]).with_mode(mode);
+            let a = dla.generate(&mut StdRng::seed_from_u64(1));
+            assert_eq!(a, dla.generate(&mut StdRng::seed_from_u64(1)));
+            assert_ne!(a, dla.generate(&mut StdRng::seed_from_u64(2)));
+            println!("{}\n", tiles_to_string(&a));
+        }
+    }
+
+    #[test]
+    fn grows_connected() {
+        for mode in [DlaMode::WalkInwards, DlaMode::WalkOutwards] {
+            let grid = Dla::new([40, 30]).with_mode(mode).generate(&mut StdRng::seed_from_u64(4));
+            let (reached, floors) = connected(&grid, IVec2::new(19, 14));
+            // 25% of the 38x28 cells inside the edge.
+            assert_eq!(floors, 266);
+            assert_eq!(reached, floors, "{mode:?}");
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::prelude::*;
+use banana_direction::prelude::*;
+use banana_grid::prelude::*;
+use rand::Rng;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Where each new drunkard starts walking from.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum DrunkSpawnMode {
+    /// Every drunkard starts in the middle of the map.
+    #[default]
+    Center,
+    /// Each drunkard starts on a random floor tile dug by an earlier one.
+    RandomFloor,
+    /// Each drunkard starts anywhere on the map.
+    Anywhere,
+}
+
+/// Maps dug out by drunkards stumbling around at random.
+///
+/// Drunkards are spawned one after the other until enough of the map is floor. The edge
+/// of the map is never dug.
+///
+/// See: [Random Walk Cave Generation](http://www.roguebasin.com/index.php/Random_Walk_Cave_Generation)
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrunkardsWalk {
+    /// The size of the generated map.
+    pub size: UVec2,
+    pub spawn_mode: DrunkSpawnMode,
+    /// How many steps each drunkard takes, or `None` to walk until enough is dug.
+    pub lifetime: Option<u32>,
+    /// Stop once this fraction of the map is floor.
+    pub floor_percent: f32,
+    /// Whether drunkards may also stumble diagonally.
+    pub diagonal: bool,
+    /// Give up after this many drunkards, in case the floor target can't be reached.
+    pub max_drunkards: u32,
+}
+
+impl DrunkardsWalk {
+    pub fn new(size: impl Size2d) -> Self {
+        Self {
+            size: size.as_uvec2(),
+            spawn_mode: DrunkSpawnMode::Center,
+            lifetime: Some(400),
+            floor_percent: 0.5,
+            diagonal: false,
+            max_drunkards: 10_000,
+        }
+    }
+
+    #[must_use]
+    pub fn with_spawn_mode(mut self, spawn_mode: DrunkSpawnMode) -> Self {
+        self.spawn_mode = spawn_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn with_lifetime(mut self, lifetime: Option<u32>) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    #[must_use]
+    pub fn with_floor_percent(mut self, floor_percent: f32) -> Self {
+        self.floor_percent = floor_percent;
+        self
+    }
+
+    #[must_use]
+    pub fn with_diagonal(mut self, diagonal: bool) -> Self {
+        self.diagonal = diagonal;
+        self
+    }
+
+    /// Generate a map, drawing every step from `rng`.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Grid<Tile> {
+        let mut grid = Grid::new(self.size, Tile::Wall);
+        let Some(inner) = Inner::new(self.size) else { return grid };
+
+        let target = (inner.area() as f32 * self.floor_percent.clamp(0.0, 1.0)).ceil() as usize;
+        let mut floors = Vec::new();
+        for _ in 0..self.max_drunkards {
+            let mut point = match self.spawn_mode {
+                DrunkSpawnMode::Center => inner.center(),
+                DrunkSpawnMode::RandomFloor if !floors.is_empty() => {
+                    floors[rng.gen_range(0..floors.len())]
+                }
+                DrunkSpawnMode::RandomFloor => inner.center(),
+                DrunkSpawnMode::Anywhere => inner.random(rng),
+            };
+
+            let mut steps = 0;
+            while self.lifetime.is_none_or(|lifetime| steps < lifetime) && floors.len() < target {
+                if grid[point].is_wall() {
+                    grid[point] = Tile::Floor;
+                    floors.push(point);
+                }
+                point = inner.clamp(point + random_step(rng, self.diagonal));
+                steps += 1;
+            }
+
+            if floors.len() >= target {
+                break;
+            }
+        }
+
+        grid
+    }
+}
+
+/// A random step to a neighbor, using [`CardinalDirection`]s only unless `diagonal`.
+pub(crate) fn random_step<R: Rng + ?Sized>(rng: &mut R, diagonal: bool) -> IVec2 {
+    if diagonal {
+        rng.gen::<Direction>().coord()
+    } else {
+        rng.gen::<CardinalDirection>().coord()
+    }
+}
+
+/// The part of a map inside its edge, which walkers stay within.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Inner {
+    min: IVec2,
+    max: IVec2,
+}
+
+impl Inner {
+    /// `None` if the map is too small to have anything inside its edge.
+    pub(crate) fn new(size: UVec2) -> Option<Self> {
+        let max = size.as_ivec2() - IVec2::splat(2);
+        max.cmpge(IVec2::ONE).all().then_some(Self { min: IVec2::ONE, max })
+    }
+
+    pub(crate) fn area(self) -> usize {
+        let size = self.max - self.min + IVec2::ONE;
+        (size.x * size.y) as usize
+    }
+
+    pub(crate) fn center(self) -> IVec2 {
+        (self.min + self.max) / 2
+    }
+
+    pub(crate) fn contains(self, point: IVec2) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    pub(crate) fn clamp(self, point: IVec2) -> IVec2 {
+        point.clamp(self.min, self.max)
+    }
+
+    pub(crate) fn random<R: Rng + ?Sized>(self, rng: &mut R) -> IVec2 {
+        IVec2::new(rng.gen_range(self.min.x..=self.max.x), rng.gen_range(self.min.y..=self.max.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn floor_count(grid: &Grid<Tile>) -> usize {
+        grid.cells.iter().filter(|t| t.is_floor()).count()
+    }
+
+    #[test]
+    fn seeded() {
+        let walk = DrunkardsWalk::new([60, 30]);
+        let a = walk.generate(&mut StdRng::seed_from_u64(5));
+        assert_eq!(a, walk.generate(&mut StdRng::seed_from_u64(5)));
+        assert_ne!(a, walk.generate(&mut StdRng::seed_from_u64(6)));
+        println!("{}", tiles_to_string(&a));
+    }
+
+    #[test]
+    fn floor_percent() {
+        for mode in [DrunkSpawnMode::Center, DrunkSpawnMode::RandomFloor, DrunkSpawnMode::Anywhere]
+        {
+            for diagonal in [false, true] {
+                let grid = DrunkardsWalk::new([40, 22])
+                    .with_spawn_mode(mode)
+                    .with_diagonal(diagonal)
+                    .with_floor_percent(0.4)
+                    .generate(&mut StdRng::seed_from_u64(9));
+
+                // 40% of the 38x20 cells inside the edge.
+                assert_eq!(floor_count(&grid), 304, "{mode:?}");
+                for p in grid.size().iter().filter(|p| grid[*p].is_floor()) {
+                    assert!(p.x > 0 && p.y > 0 && p.x < 39 && p.y < 21);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lifetime() {
+        // A single drunkard with a short life can't dig much.
+        let mut walk = DrunkardsWalk::new([40, 40]).with_lifetime(Some(20));
+        walk.max_drunkards = 1;
+        let grid = walk.generate(&mut StdRng::seed_from_u64(2));
+        assert!((1..=20).contains(&floor_count(&grid)));
+        assert!(grid[IVec2::new(19, 19)].is_floor());
+
+        let grid = walk.with_lifetime(None).generate(&mut StdRng::seed_from_u64(2));
+        assert_eq!(floor_count(&grid), 38 * 38 / 2);
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "rng")]
+mod bsp;
+#[cfg(feature = "rng")]
+mod cellular_automata;
+mod connect;
+mod corridor;
+#[cfg(feature = "rng")]
+mod dla;
+#[cfg(feature = "rng")]
+mod drunkards_walk;
+mod noise;
+mod prefab;
+mod regions;
+mod tile;
+mod voronoi;
+#[cfg(feature = "rng")]
+mod wfc;
+
+pub mod prelude {
+    #[cfg(feature = "rng")]
+    pub use crate::bsp::*;
+    #[cfg(feature = "rng")]
+    pub use crate::cellular_automata::*;
+    pub use crate::connect::*;
+    pub use crate::corridor::*;
+    #[cfg(feature = "rng")]
+    pub use crate::dla::*;
+    #[cfg(feature = "rng")]
+    pub use crate::drunkards_walk::*;
+    pub use crate::noise::*;
+    pub use crate::prefab::*;
+    pub use crate::regions::*;
+    pub use crate::tile::*;
+    pub use crate::voronoi::*;
+    #[cfg(feature = "rng")]
+    pub use crate::wfc::*;
+}
@@ -0,0 +1,163 @@
+use super::Noise;
+use banana_grid::prelude::*;
+
+/// Shifts each octave away from the last, so they don't all line up at the origin.
+const OCTAVE_SHIFT: Vec2 = Vec2::new(19.19, 7.31);
+
+/// Fractal Brownian motion: octaves of a noise layered at rising frequency and falling
+/// amplitude, adding ever finer detail.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fbm<N> {
+    pub noise: N,
+    /// How many octaves are layered.
+    pub octaves: u32,
+    /// How much the frequency grows from one octave to the next.
+    pub lacunarity: f32,
+    /// How much the amplitude shrinks from one octave to the next.
+    pub gain: f32,
+}
+
+impl<N> Fbm<N> {
+    pub fn new(noise: N) -> Self {
+        Self { noise, octaves: 5, lacunarity: 2.0, gain: 0.5 }
+    }
+
+    #[must_use]
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves;
+        self
+    }
+
+    #[must_use]
+    pub fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    #[must_use]
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<N: Noise> Noise for Fbm<N> {
+    fn get(&self, point: Vec2) -> f32 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        for octave in 0..self.octaves {
+            let shift = OCTAVE_SHIFT * octave as f32;
+            sum += self.noise.get(point * frequency + shift) * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total > 0.0 {
+            sum / total
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Musgrave's ridged multifractal: sharp ridges along where the noise crosses zero,
+/// with the finer octaves weighted towards the ridges. Good for mountain ranges.
+///
+/// See: [Texturing and Modeling: A Procedural Approach](https://www.csee.umbc.edu/~ebert/book/book.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RidgedMulti<N> {
+    pub noise: N,
+    /// How many octaves are layered.
+    pub octaves: u32,
+    /// How much the frequency grows from one octave to the next.
+    pub lacunarity: f32,
+    /// How strongly each octave is confined to the ridges of the last.
+    pub gain: f32,
+    /// Raises the ridges. Above 1, the valleys fill in.
+    pub offset: f32,
+}
+
+impl<N> RidgedMulti<N> {
+    pub fn new(noise: N) -> Self {
+        Self { noise, octaves: 5, lacunarity: 2.0, gain: 2.0, offset: 1.0 }
+    }
+
+    #[must_use]
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves;
+        self
+    }
+
+    #[must_use]
+    pub fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    #[must_use]
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    #[must_use]
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl<N: Noise> Noise for RidgedMulti<N> {
+    fn get(&self, point: Vec2) -> f32 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let mut weight = 1.0;
+        for octave in 0..self.octaves {
+            let shift = OCTAVE_SHIFT * octave as f32;
+            let ridge = self.offset - self.noise.get(point * frequency + shift).abs();
+            let signal = ridge * ridge * weight;
+            weight = (signal * self.gain).clamp(0.0, 1.0);
+
+            sum += signal * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude /= self.lacunarity;
+        }
+
+        // Each octave is at most the offset squared, so this spans -1 to 1.
+        let peak = total * self.offset * self.offset;
+        if peak > 0.0 {
+            sum / peak * 2.0 - 1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn single_octave() {
+        let fbm = Fbm::new(Perlin::new(3)).with_octaves(1);
+        let perlin = Perlin::new(3);
+        for p in UVec2::new(20, 20).iter() {
+            let p = p.as_vec2() * 0.37;
+            assert_eq!(fbm.get(p), perlin.get(p));
+        }
+        assert_eq!(fbm.with_octaves(0).get(Vec2::ONE), 0.0);
+    }
+
+    #[test]
+    fn ridges() {
+        // The ridges sit where the noise crosses zero, as at Perlin's lattice points.
+        let ridged = RidgedMulti::new(Perlin::new(3)).with_octaves(1);
+        assert_eq!(ridged.get(Vec2::new(4.0, -2.0)), 1.0);
+        let p = Vec2::new(4.3, -2.8);
+        let ridge = 1.0 - Perlin::new(3).get(p).abs();
+        assert_eq!(ridged.get(p), ridge * ridge * 2.0 - 1.0);
+        assert!(ridged.get(p) < 1.0);
+    }
+}
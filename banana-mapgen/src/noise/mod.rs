@@ -0,0 +1,154 @@
+use banana_grid::prelude::*;
+
+mod fractal;
+mod open_simplex;
+mod perlin;
+mod value;
+
+pub use fractal::*;
+pub use open_simplex::*;
+pub use perlin::*;
+pub use value::*;
+
+/// Smooth noise over the plane, the same for the same seed and point every time.
+pub trait Noise {
+    /// The noise at a point, roughly between -1 and 1.
+    fn get(&self, point: Vec2) -> f32;
+
+    /// Sample the noise at every cell of a new grid, with `scale` being the distance
+    /// between neighboring cells in noise space.
+    ///
+    /// Works for any grid, for example:
+    ///
+    /// ```
+    /// # use banana_mapgen::prelude::*;
+    /// # use banana_grid::prelude::*;
+    /// let heights: Grid2D<f32> = Perlin::new(1).to_grid([64, 32], 0.1);
+    /// ```
+    fn to_grid<G>(&self, size: impl Size2d, scale: f32) -> G
+    where
+        G: GridNew<f32>,
+        Self: Sized,
+    {
+        G::new_fn(size, |p| self.get(p.as_vec2() * scale))
+    }
+}
+
+impl<N: Noise + ?Sized> Noise for &N {
+    #[inline]
+    fn get(&self, point: Vec2) -> f32 {
+        (**self).get(point)
+    }
+}
+
+/// A shuffle of 0..256 picked by the seed, hashing lattice points into pseudo-random
+/// bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Permutation([u8; 256]);
+
+impl Permutation {
+    pub(crate) fn new(seed: u64) -> Self {
+        // splitmix64, so seeds don't need the rng feature.
+        let mut state = seed;
+        let mut next = move || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+
+        let mut table = [0; 256];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+        for i in (1..256).rev() {
+            table.swap(i, (next() % (i as u64 + 1)) as usize);
+        }
+        Self(table)
+    }
+
+    #[inline]
+    pub(crate) fn hash(&self, point: IVec2) -> u8 {
+        let x = self.0[(point.x & 255) as usize];
+        self.0[((i32::from(x) + point.y) & 255) as usize]
+    }
+}
+
+/// Perlin's quintic ease curve, flat at both ends so cells join up smoothly.
+#[inline]
+pub(crate) fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    fn noises(seed: u64) -> Vec<Box<dyn Noise>> {
+        vec![
+            Box::new(Perlin::new(seed)),
+            Box::new(OpenSimplex::new(seed)),
+            Box::new(ValueNoise::new(seed)),
+            Box::new(Fbm::new(Perlin::new(seed))),
+            Box::new(RidgedMulti::new(OpenSimplex::new(seed))),
+        ]
+    }
+
+    fn points() -> impl Iterator<Item = Vec2> {
+        (0..200 * 200).map(|i| Vec2::new((i % 200) as f32, (i / 200) as f32) * 0.173 - 17.0)
+    }
+
+    #[test]
+    fn seeded() {
+        for (a, (b, c)) in noises(5).iter().zip(noises(5).iter().zip(noises(6).iter())) {
+            assert!(points().all(|p| a.get(p) == b.get(p)));
+            assert!(points().any(|p| a.get(p) != c.get(p)));
+        }
+    }
+
+    #[test]
+    fn range() {
+        for noise in noises(9) {
+            let values: Vec<f32> = points().map(|p| noise.get(p)).collect();
+            let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            assert!(min >= -1.0 && max <= 1.0, "{min} {max}");
+            assert!(max - min > 0.8, "{min} {max}");
+        }
+    }
+
+    #[test]
+    fn smooth() {
+        for noise in noises(2) {
+            for p in points() {
+                let step = (noise.get(p) - noise.get(p + Vec2::new(0.001, 0.0005))).abs();
+                assert!(step < 0.05, "{p} {step}");
+            }
+        }
+    }
+
+    #[test]
+    fn grids_agree() {
+        let noise = Fbm::new(OpenSimplex::new(3));
+        let grid: Grid<f32> = noise.to_grid([40, 30], 0.05);
+        let grid_2d: Grid2D<f32> = noise.to_grid([40, 30], 0.05);
+        for p in grid.size().iter() {
+            assert_eq!(grid[p], grid_2d[p]);
+            assert_eq!(grid[p], noise.get(p.as_vec2() * 0.05));
+        }
+
+        let shades = [' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+        for row in grid.cells.chunks(40) {
+            let row: String =
+                row.iter().map(|v| shades[((v + 1.0) * 4.99).clamp(0.0, 9.0) as usize]).collect();
+            println!("{row}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::{Noise, Permutation};
+use banana_grid::prelude::*;
+use std::f32::consts::TAU;
+
+/// Skews the plane so the triangular simplex lattice lines up with the integer grid.
+const SKEW: f32 = 0.366_025_4;
+/// Undoes [`SKEW`].
+const UNSKEW: f32 = 0.211_324_87;
+/// The squared radius of each lattice point's influence.
+const RADIUS_SQUARED: f32 = 2.0 / 3.0;
+/// Brings the sum of the contributions back to between -1 and 1.
+const NORMALIZE: f32 = 18.1;
+
+/// Gradient noise on a triangular lattice, with the wide falloff of OpenSimplex2.
+///
+/// Compared to [`Perlin`](super::Perlin), it has no visible square grid and no zero at
+/// every integer point.
+///
+/// See: [OpenSimplex2](https://github.com/KdotJPG/OpenSimplex2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenSimplex {
+    permutation: Permutation,
+}
+
+impl OpenSimplex {
+    pub fn new(seed: u64) -> Self {
+        Self { permutation: Permutation::new(seed) }
+    }
+
+    /// One of 24 evenly spread unit gradients, none of them along an axis.
+    fn gradient(&self, vertex: IVec2) -> Vec2 {
+        let index = f32::from(self.permutation.hash(vertex) % 24);
+        let (sin, cos) = ((index + 0.5) * TAU / 24.0).sin_cos();
+        Vec2::new(cos, sin)
+    }
+}
+
+impl Noise for OpenSimplex {
+    fn get(&self, point: Vec2) -> f32 {
+        let skewed = point + (point.x + point.y) * SKEW;
+        let base = skewed.floor().as_ivec2();
+
+        // The falloff reaches past the triangle the point is in, so every lattice point
+        // near enough to matter is summed.
+        let mut value = 0.0;
+        for y in -1..=2 {
+            for x in -1..=2 {
+                let vertex = base + IVec2::new(x, y);
+                let corner = vertex.as_vec2() - (vertex.x + vertex.y) as f32 * UNSKEW;
+                let offset = point - corner;
+                let falloff = RADIUS_SQUARED - offset.length_squared();
+                if falloff > 0.0 {
+                    value += falloff.powi(4) * self.gradient(vertex).dot(offset);
+                }
+            }
+        }
+        value * NORMALIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn not_zero_at_lattice() {
+        let noise = OpenSimplex::new(1);
+        let zeros = UVec2::new(10, 10).iter().filter(|p| noise.get(p.as_vec2()) == 0.0).count();
+        assert!(zeros < 5);
+    }
+}
@@ -0,0 +1,64 @@
+use super::{fade, lerp, Noise, Permutation};
+use banana_grid::prelude::*;
+use std::f32::consts::FRAC_1_SQRT_2;
+
+/// Perlin's gradient noise. It is zero at every integer point, with hills and valleys
+/// in between.
+///
+/// See: [Improving Noise](https://mrl.nyu.edu/~perlin/paper445.pdf)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        Self { permutation: Permutation::new(seed) }
+    }
+
+    fn corner(&self, cell: IVec2, corner: IVec2, offset: Vec2) -> f32 {
+        const GRADIENTS: [Vec2; 8] = [
+            Vec2::X,
+            Vec2::Y,
+            Vec2::NEG_X,
+            Vec2::NEG_Y,
+            Vec2::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+            Vec2::new(-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+            Vec2::new(-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            Vec2::new(FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+        ];
+        let gradient = GRADIENTS[usize::from(self.permutation.hash(cell + corner) & 7)];
+        gradient.dot(offset - corner.as_vec2())
+    }
+}
+
+impl Noise for Perlin {
+    fn get(&self, point: Vec2) -> f32 {
+        let floor = point.floor();
+        let cell = floor.as_ivec2();
+        let offset = point - floor;
+
+        let (u, v) = (fade(offset.x), fade(offset.y));
+        let top =
+            lerp(self.corner(cell, IVec2::ZERO, offset), self.corner(cell, IVec2::X, offset), u);
+        let bottom =
+            lerp(self.corner(cell, IVec2::Y, offset), self.corner(cell, IVec2::ONE, offset), u);
+        // With unit gradients, the largest possible value is half the square root of 2.
+        lerp(top, bottom, v) * std::f32::consts::SQRT_2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn zero_at_lattice() {
+        let perlin = Perlin::new(4);
+        for p in UVec2::new(10, 10).iter() {
+            assert_eq!(perlin.get((p - 5).as_vec2()), 0.0);
+        }
+        assert_ne!(perlin.get(Vec2::new(0.5, 0.3)), 0.0);
+    }
+}
@@ -0,0 +1,49 @@
+use super::{fade, lerp, Noise, Permutation};
+use banana_grid::prelude::*;
+
+/// Value noise: a random value at every integer point, smoothly blended in between.
+///
+/// Cheaper than [`Perlin`](super::Perlin), but blockier, with its features lined up on
+/// the axes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueNoise {
+    permutation: Permutation,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u64) -> Self {
+        Self { permutation: Permutation::new(seed) }
+    }
+
+    /// The value at an integer point, between -1 and 1.
+    #[inline]
+    fn lattice(&self, point: IVec2) -> f32 {
+        f32::from(self.permutation.hash(point)) / 127.5 - 1.0
+    }
+}
+
+impl Noise for ValueNoise {
+    fn get(&self, point: Vec2) -> f32 {
+        let floor = point.floor();
+        let cell = floor.as_ivec2();
+        let offset = point - floor;
+
+        let (u, v) = (fade(offset.x), fade(offset.y));
+        let top = lerp(self.lattice(cell), self.lattice(cell + IVec2::X), u);
+        let bottom = lerp(self.lattice(cell + IVec2::Y), self.lattice(cell + IVec2::ONE), u);
+        lerp(top, bottom, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn blends_lattice() {
+        let noise = ValueNoise::new(8);
+        let (a, b) = (noise.get(Vec2::new(2.0, 7.0)), noise.get(Vec2::new(3.0, 7.0)));
+        assert_eq!(noise.get(Vec2::new(2.5, 7.0)), (a + b) / 2.0);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::prelude::*;
+use banana_geometry::prelude::Rect;
+use banana_grid::prelude::*;
+use std::fmt;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Why a [`Prefab`] could not be loaded or stamped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefabError {
+    /// The text has no rows.
+    Empty,
+    /// This row is not as wide as the first one.
+    RaggedRow(usize),
+    /// The prefab would stick out of the grid.
+    OutOfBounds,
+    /// The prefab would overlap this occupied area.
+    Collision(Rect),
+}
+
+impl fmt::Display for PrefabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrefabError::Empty => write!(f, "the prefab has no rows"),
+            PrefabError::RaggedRow(row) => write!(f, "row {row} is not as wide as the first"),
+            PrefabError::OutOfBounds => write!(f, "the prefab does not fit in the grid"),
+            PrefabError::Collision(rect) => {
+                write!(f, "the prefab overlaps {} to {}", rect.min, rect.max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrefabError {}
+
+/// A small hand-made piece of map, such as a vault, to be stamped into generated maps.
+///
+/// Cells that are `None` are transparent, and leave the map underneath untouched.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct Prefab<T> {
+    grid: Grid<Option<T>>,
+}
+
+impl<T: Copy> Prefab<T> {
+    pub fn new(grid: Grid<Option<T>>) -> Self {
+        Self { grid }
+    }
+
+    /// Load a prefab from rows of text, turning each character into a cell with `parse`.
+    ///
+    /// Blank lines before the first row and after the last are ignored, so prefabs can be
+    /// written as multi-line string literals. Every row must be as wide as the first.
+    pub fn from_ascii<F>(text: &str, mut parse: F) -> Result<Self, PrefabError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let rows: Vec<Vec<char>> = text
+            .lines()
+            .skip_while(|line| line.trim().is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+        let height = rows.iter().rposition(|row| row.iter().any(|c| !c.is_whitespace()));
+        let Some(height) = height.map(|last| last + 1) else { return Err(PrefabError::Empty) };
+
+        let width = rows[0].len();
+        if let Some(row) = rows[..height].iter().position(|row| row.len() != width) {
+            return Err(PrefabError::RaggedRow(row));
+        }
+
+        let size = UVec2::new(width as u32, height as u32);
+        Ok(Self::new(Grid::new_fn(size, |p| parse(rows[p.y as usize][p.x as usize]))))
+    }
+
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.grid.size()
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.grid.width()
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.grid.height()
+    }
+
+    /// The cell at a point, or `None` if it is transparent or outside the prefab.
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<T> {
+        self.grid.get(point).copied().flatten()
+    }
+
+    #[inline]
+    pub fn grid(&self) -> &Grid<Option<T>> {
+        &self.grid
+    }
+
+    /// The prefab turned a quarter turn clockwise.
+    #[must_use]
+    pub fn rotate90(&self) -> Self {
+        Self::new(self.grid.rotate90())
+    }
+
+    /// The prefab turned half a turn.
+    #[must_use]
+    pub fn rotate180(&self) -> Self {
+        Self::new(self.grid.rotate180())
+    }
+
+    /// The prefab turned a quarter turn counter-clockwise.
+    #[must_use]
+    pub fn rotate270(&self) -> Self {
+        Self::new(self.grid.rotate270())
+    }
+
+    /// The prefab mirrored along `axis`: [`Axis::X`] swaps left and right, [`Axis::Y`]
+    /// swaps top and bottom.
+    #[must_use]
+    pub fn mirror(&self, axis: Axis) -> Self {
+        Self::new(self.grid.flip(axis))
+    }
+
+    /// The area the prefab covers when stamped at `position`, its maximum corner
+    /// excluded.
+    #[inline]
+    pub fn bounds_at(&self, position: impl GridPoint) -> Rect {
+        Rect::new(position.as_ivec2(), self.size().as_ivec2())
+    }
+
+    /// Copies every cell that isn't transparent into `grid`, with the prefab's top left
+    /// corner at `position`, returning the area covered.
+    ///
+    /// Nothing is written if the prefab would stick out of `grid` or overlap any of the
+    /// `occupied` areas, whose maximum corners are excluded.
+    pub fn stamp(
+        &self,
+        grid: &mut Grid<T>,
+        position: impl GridPoint,
+        occupied: &[Rect],
+    ) -> Result<Rect, PrefabError> {
+        let bounds = self.bounds_at(position);
+        if bounds.min.cmplt(IVec2::ZERO).any() || bounds.max.cmpgt(grid.size().as_ivec2()).any() {
+            return Err(PrefabError::OutOfBounds);
+        }
+        if let Some(rect) = occupied.iter().find(|rect| overlaps(&bounds, rect)) {
+            return Err(PrefabError::Collision(*rect));
+        }
+
+        for p in self.size().iter() {
+            if let Some(value) = self.grid[p] {
+                grid[bounds.min + p] = value;
+            }
+        }
+        Ok(bounds)
+    }
+}
+
+impl Prefab<Tile> {
+    /// Load a prefab of tiles, `#` being a wall, `.` a floor and anything else
+    /// transparent.
+    pub fn from_tiles(text: &str) -> Result<Self, PrefabError> {
+        Self::from_ascii(text, |c| match c {
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Floor),
+            _ => None,
+        })
+    }
+}
+
+/// Whether two areas share any cell, their maximum corners excluded.
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.min.cmplt(b.max).all() && b.min.cmplt(a.max).all()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::Rect;
+    use banana_grid::prelude::*;
+
+    fn vault() -> Prefab<Tile> {
+        Prefab::from_tiles(
+            "
+###
+#..
+  .
+",
+        )
+        .unwrap()
+    }
+
+    fn rows(prefab: &Prefab<Tile>) -> Vec<String> {
+        prefab
+            .grid()
+            .cells
+            .chunks(prefab.width() as usize)
+            .map(|row| row.iter().map(|t| t.map_or(' ', Tile::glyph)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn from_ascii() {
+        let vault = vault();
+        assert_eq!(vault.size(), UVec2::new(3, 3));
+        assert_eq!(vault.get([0, 0]), Some(Tile::Wall));
+        assert_eq!(vault.get([2, 1]), Some(Tile::Floor));
+        assert_eq!(vault.get([0, 2]), None);
+
+        assert_eq!(Prefab::from_tiles("\n  \n"), Err(PrefabError::Empty));
+        assert_eq!(Prefab::from_tiles("##\n#\n"), Err(PrefabError::RaggedRow(1)));
+        let numbers = Prefab::from_ascii("1 2\n345", |c| c.to_digit(10)).unwrap();
+        assert_eq!(numbers.get([2, 0]), Some(2));
+        assert_eq!(numbers.get([1, 0]), None);
+    }
+
+    #[test]
+    fn rotate() {
+        let vault = vault();
+        assert_eq!(rows(&vault.rotate90()), [" ##", " .#", "..#"]);
+        assert_eq!(rows(&vault.rotate180()), [".  ", "..#", "###"]);
+        assert_eq!(rows(&vault.rotate270()), ["#..", "#. ", "## "]);
+        assert_eq!(vault.rotate90().rotate90(), vault.rotate180());
+        assert_eq!(vault.rotate90().rotate270(), vault);
+
+        let wide = Prefab::from_tiles("#..").unwrap();
+        assert_eq!(rows(&wide.rotate90()), ["#", ".", "."]);
+        assert_eq!(rows(&wide.rotate270()), [".", ".", "#"]);
+    }
+
+    #[test]
+    fn mirror() {
+        let vault = vault();
+        assert_eq!(rows(&vault.mirror(Axis::X)), ["###", "..#", ".  "]);
+        assert_eq!(rows(&vault.mirror(Axis::Y)), ["  .", "#..", "###"]);
+        assert_eq!(vault.mirror(Axis::X).mirror(Axis::Y), vault.rotate180());
+    }
+
+    #[test]
+    fn stamp() {
+        let mut grid = Grid::new([6, 5], Tile::Floor);
+        let bounds = vault().stamp(&mut grid, [1, 1], &[]).unwrap();
+        assert_eq!(bounds, Rect::new(IVec2::ONE, IVec2::splat(3)));
+        assert_eq!(tiles_to_string(&grid), "......\n.###..\n.#....\n......\n......");
+
+        // Transparent cells keep what was there.
+        let mut walls = Grid::new([3, 3], Tile::Wall);
+        vault().stamp(&mut walls, [0, 0], &[]).unwrap();
+        assert_eq!(tiles_to_string(&walls), "###\n#..\n##.");
+
+        let before = grid.clone();
+        assert_eq!(vault().stamp(&mut grid, [4, 0], &[]), Err(PrefabError::OutOfBounds));
+        assert_eq!(vault().stamp(&mut grid, [-1, 0], &[]), Err(PrefabError::OutOfBounds));
+        assert_eq!(
+            vault().stamp(&mut grid, [3, 2], &[bounds]),
+            Err(PrefabError::Collision(bounds))
+        );
+        assert_eq!(grid, before);
+
+        // Right next to an occupied area is fine.
+        assert!(vault()
+            .stamp(&mut grid, [3, 2], &[Rect::new(IVec2::ZERO, IVec2::splat(2))])
+            .is_ok());
+        assert!(vault().stamp(&mut grid, [3, 0], &[bounds]).is_err());
+        assert!(vault()
+            .stamp(&mut grid, [1, 1], &[Rect::new(IVec2::new(4, 0), IVec2::ONE)])
+            .is_ok());
+    }
+}
@@ -0,0 +1,258 @@
+use banana_direction::prelude::*;
+use banana_geometry::prelude::Rect;
+use banana_grid::prelude::*;
+use std::collections::VecDeque;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Every cell connected to `start` through cells matching `predicate`, in the order they
+/// were reached. Empty if `start` itself doesn't match.
+///
+/// Grids read with an `EdgeMode` are filled across the edges the mode wraps.
+pub fn flood_fill<T, G, F>(
+    grid: &G,
+    start: impl GridPoint,
+    connectivity: Neighborhood,
+    mut predicate: F,
+) -> Vec<IVec2>
+where
+    G: GridRead<T>,
+    F: FnMut(&T) -> bool,
+{
+    let start = start.as_ivec2();
+    if !grid.in_bounds(start) || !grid.get(start).is_some_and(&mut predicate) {
+        return Vec::new();
+    }
+
+    let mut seen = Grid::new(grid.size(), false);
+    let mut filled = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    seen[start] = true;
+    while let Some(point) = queue.pop_front() {
+        filled.push(point);
+        for direction in connectivity.directions() {
+            let Some(next) = grid.resolve(point + direction.coord()) else { continue };
+            if seen.get(next) == Some(&false) && grid.get(next).is_some_and(&mut predicate) {
+                seen[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    filled
+}
+
+/// One connected region found by [`Regions`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    /// How many cells are in the region.
+    pub size: usize,
+    /// The smallest rectangle around the region, its maximum corner excluded.
+    pub bounds: Rect,
+    /// The average position of the region's cells.
+    pub centroid: Vec2,
+}
+
+/// The connected regions of the cells matching a predicate, such as the separate open
+/// areas of a map.
+///
+/// Regions are labelled from 0 in the row-major order of their first cell.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regions {
+    labels: Grid<Option<u32>>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Label the connected regions of the cells of `grid` that match `predicate`.
+    pub fn new<T, G, F>(grid: &G, connectivity: Neighborhood, mut predicate: F) -> Self
+    where
+        G: GridRead<T>,
+        F: FnMut(&T) -> bool,
+    {
+        let mut labels = Grid::new(grid.size(), None);
+        let mut regions = Vec::new();
+
+        for start in grid.size().iter() {
+            if labels[start].is_some() || !grid.get(start).is_some_and(&mut predicate) {
+                continue;
+            }
+
+            let label = regions.len() as u32;
+            let (mut min, mut max, mut sum) = (start, start, Vec2::ZERO);
+            let mut size = 0;
+            let mut queue = VecDeque::from([start]);
+            labels[start] = Some(label);
+            while let Some(point) = queue.pop_front() {
+                size += 1;
+                min = min.min(point);
+                max = max.max(point);
+                sum += point.as_vec2();
+                for direction in connectivity.directions() {
+                    let Some(next) = grid.resolve(point + direction.coord()) else { continue };
+                    if labels.get(next) == Some(&None) && grid.get(next).is_some_and(&mut predicate)
+                    {
+                        labels[next] = Some(label);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            regions.push(Region {
+                size,
+                bounds: Rect::from_corners(min, max + IVec2::ONE),
+                centroid: sum / size as f32,
+            });
+        }
+
+        Self { labels, regions }
+    }
+
+    /// The label of the region a cell belongs to, or `None` if it belongs to none.
+    #[inline]
+    pub fn label(&self, point: impl GridPoint) -> Option<u32> {
+        self.labels.get(point).copied().flatten()
+    }
+
+    #[inline]
+    pub fn get(&self, label: u32) -> Option<&Region> {
+        self.regions.get(label as usize)
+    }
+
+    #[inline]
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// The label of the region with the most cells, the first one on a tie.
+    pub fn largest(&self) -> Option<u32> {
+        let mut largest: Option<(u32, usize)> = None;
+        for (label, region) in self.regions.iter().enumerate() {
+            if largest.is_none_or(|(_, size)| region.size > size) {
+                largest = Some((label as u32, region.size));
+            }
+        }
+        largest.map(|(label, _)| label)
+    }
+
+    /// Every cell in the region with `label`.
+    pub fn cells(&self, label: u32) -> impl Iterator<Item = IVec2> + '_ {
+        self.labels.size().iter().filter(move |p| self.labels[*p] == Some(label))
+    }
+
+    #[inline]
+    pub fn labels(&self) -> &Grid<Option<u32>> {
+        &self.labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::Neighborhood;
+    use banana_geometry::prelude::Rect;
+    use banana_grid::prelude::*;
+
+    fn map(text: &str) -> Grid<bool> {
+        let rows: Vec<&str> = text.trim().lines().map(str::trim).collect();
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| rows[p.y as usize].as_bytes()[p.x as usize] == b'.')
+    }
+
+    #[test]
+    fn fill() {
+        let grid = map("
+            ..#..
+            ..#..
+            ###..
+            ...#.
+        ");
+        let mut filled = flood_fill(&grid, [0, 0], Neighborhood::Cardinal, |floor| *floor);
+        filled.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(
+            filled,
+            vec![IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(0, 1), IVec2::new(1, 1)]
+        );
+
+        assert_eq!(flood_fill(&grid, [3, 0], Neighborhood::Cardinal, |floor| *floor).len(), 7);
+        assert_eq!(flood_fill(&grid, [3, 0], Neighborhood::All, |floor| *floor).len(), 10);
+        assert!(flood_fill(&grid, [2, 0], Neighborhood::Cardinal, |floor| *floor).is_empty());
+        assert!(flood_fill(&grid, [9, 9], Neighborhood::Cardinal, |floor| *floor).is_empty());
+    }
+
+    #[test]
+    fn edge_modes() {
+        let grid = map("
+            ..#..
+            ..#..
+            ###..
+            ...#.
+        ");
+        let wrapped = grid.with_edges(EdgeMode::WrapX);
+        assert_eq!(flood_fill(&wrapped, [0, 0], Neighborhood::Cardinal, |floor| *floor).len(), 14);
+        assert_eq!(Regions::new(&wrapped, Neighborhood::Cardinal, |floor| *floor).len(), 1);
+
+        let open = grid.with_edges(EdgeMode::Constant(true));
+        assert_eq!(flood_fill(&open, [0, 0], Neighborhood::Cardinal, |floor| *floor).len(), 4);
+        assert_eq!(Regions::new(&open, Neighborhood::Cardinal, |floor| *floor).len(), 3);
+    }
+
+    #[test]
+    fn labels() {
+        let grid = map("
+            ..##..
+            ..#.#.
+            ##.##.
+            ....#.
+        ");
+        let four = Regions::new(&grid, Neighborhood::Cardinal, |floor| *floor);
+        assert_eq!(four.len(), 4);
+        assert_eq!(four.label([0, 0]), Some(0));
+        assert_eq!(four.label([4, 0]), Some(1));
+        assert_eq!(four.label([5, 3]), Some(1));
+        assert_eq!(four.label([3, 1]), Some(2));
+        assert_eq!(four.label([2, 2]), Some(3));
+        assert_eq!(four.label([0, 3]), Some(3));
+        assert_eq!(four.label([2, 0]), None);
+        assert_eq!(four.label([9, 0]), None);
+
+        // Diagonals join them all up.
+        let eight = Regions::new(&grid, Neighborhood::All, |floor| *floor);
+        assert_eq!(eight.len(), 1);
+        assert_eq!(eight.get(0).unwrap().size, 15);
+    }
+
+    #[test]
+    fn stats() {
+        let grid = map("
+            ......
+            .##...
+            .##...
+            ......
+        ");
+        let regions = Regions::new(&grid, Neighborhood::Cardinal, |floor| !*floor);
+        assert_eq!(regions.len(), 1);
+        let region = regions.get(0).unwrap();
+        assert_eq!(region.size, 4);
+        assert_eq!(region.bounds, Rect::new(IVec2::new(1, 1), IVec2::new(2, 2)));
+        assert_eq!(region.centroid, Vec2::new(1.5, 1.5));
+        assert_eq!(regions.cells(0).count(), 4);
+
+        let floors = Regions::new(&grid, Neighborhood::Cardinal, |floor| *floor);
+        assert_eq!(floors.largest(), Some(0));
+        assert_eq!(floors.get(0).unwrap().bounds, Rect::new(IVec2::ZERO, IVec2::new(6, 4)));
+        assert!(Regions::new(&grid, Neighborhood::Cardinal, |_| false).largest().is_none());
+    }
+}
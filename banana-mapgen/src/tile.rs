@@ -0,0 +1,57 @@
+use banana_grid::prelude::*;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A single cell of a generated map.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Tile {
+    #[default]
+    Wall,
+    Floor,
+}
+
+impl Tile {
+    #[inline]
+    pub fn is_wall(self) -> bool {
+        self == Tile::Wall
+    }
+
+    #[inline]
+    pub fn is_floor(self) -> bool {
+        self == Tile::Floor
+    }
+
+    /// The glyph used when printing maps.
+    #[inline]
+    pub fn glyph(self) -> char {
+        match self {
+            Tile::Wall => '#',
+            Tile::Floor => '.',
+        }
+    }
+}
+
+impl From<bool> for Tile {
+    /// `true` is a floor, `false` a wall.
+    fn from(floor: bool) -> Self {
+        if floor {
+            Tile::Floor
+        } else {
+            Tile::Wall
+        }
+    }
+}
+
+/// Renders a map as rows of glyphs, one line per row.
+pub fn tiles_to_string(grid: &Grid<Tile>) -> String {
+    let mut out = String::with_capacity(grid.len() + grid.height() as usize);
+    for (i, tile) in grid.cells.iter().enumerate() {
+        if i > 0 && i % grid.width() as usize == 0 {
+            out.push('\n');
+        }
+        out.push(tile.glyph());
+    }
+    out
+}
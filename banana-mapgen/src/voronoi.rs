@@ -0,0 +1,165 @@
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+use std::collections::BTreeSet;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A map split into regions, each cell belonging to the seed point nearest to it.
+///
+/// Regions are labelled by the index of their seed. When a cell is as close to several
+/// seeds, it goes to the one listed first.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voronoi {
+    seeds: Vec<IVec2>,
+    labels: Grid<u32>,
+    /// The regions bordering each region, in ascending order.
+    adjacency: Vec<Vec<u32>>,
+}
+
+impl Voronoi {
+    /// The label of every cell when there are no seeds.
+    pub const NONE: u32 = u32::MAX;
+
+    /// Partition a map of `size` around `seeds`, measuring with `distance`.
+    pub fn new(
+        size: impl Size2d,
+        seeds: impl IntoIterator<Item = impl GridPoint>,
+        distance: DistanceAlg,
+    ) -> Self {
+        let seeds: Vec<IVec2> = seeds.into_iter().map(|seed| seed.as_ivec2()).collect();
+        let labels = Grid::new_fn(size, |p| {
+            let mut nearest = Self::NONE;
+            let mut lowest = f32::INFINITY;
+            for (i, seed) in seeds.iter().enumerate() {
+                let d = distance.distance2d(p, *seed);
+                if d < lowest {
+                    lowest = d;
+                    nearest = i as u32;
+                }
+            }
+            nearest
+        });
+
+        // Regions are adjacent when two of their cells share an edge.
+        let mut adjacency = vec![BTreeSet::new(); seeds.len()];
+        for p in labels.size().iter() {
+            let here = labels[p];
+            for next in [p + IVec2::X, p + IVec2::Y] {
+                match labels.get(next) {
+                    Some(&there) if there != here => {
+                        adjacency[here as usize].insert(there);
+                        adjacency[there as usize].insert(here);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let adjacency = adjacency.into_iter().map(|set| set.into_iter().collect()).collect();
+
+        Self { seeds, labels, adjacency }
+    }
+
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.labels.size()
+    }
+
+    #[inline]
+    pub fn seeds(&self) -> &[IVec2] {
+        &self.seeds
+    }
+
+    #[inline]
+    pub fn region_count(&self) -> usize {
+        self.seeds.len()
+    }
+
+    /// The region a cell belongs to, or `None` outside the map or without seeds.
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<u32> {
+        self.labels.get(point).copied().filter(|label| *label != Self::NONE)
+    }
+
+    /// The regions bordering `region`, in ascending order.
+    #[inline]
+    pub fn neighbors(&self, region: u32) -> &[u32] {
+        self.adjacency.get(region as usize).map_or(&[], Vec::as_slice)
+    }
+
+    #[inline]
+    pub fn are_adjacent(&self, a: u32, b: u32) -> bool {
+        self.neighbors(a).binary_search(&b).is_ok()
+    }
+
+    /// Every cell in `region`.
+    pub fn cells(&self, region: u32) -> impl Iterator<Item = IVec2> + '_ {
+        self.labels.size().iter().filter(move |p| self.labels[*p] == region)
+    }
+
+    #[inline]
+    pub fn labels(&self) -> &Grid<u32> {
+        &self.labels
+    }
+
+    #[inline]
+    pub fn into_labels(self) -> Grid<u32> {
+        self.labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    #[test]
+    fn nearest_seed() {
+        let seeds = [IVec2::new(2, 2), IVec2::new(12, 2), IVec2::new(7, 9)];
+        let voronoi = Voronoi::new([15, 12], seeds, DistanceAlg::Pythagoras);
+
+        assert_eq!(voronoi.region_count(), 3);
+        for (i, seed) in seeds.iter().enumerate() {
+            assert_eq!(voronoi.get(*seed), Some(i as u32));
+        }
+        for p in voronoi.size().iter() {
+            let label = voronoi.get(p).unwrap() as usize;
+            let d = DistanceAlg::Pythagoras.distance2d(p, seeds[label]);
+            assert!(seeds.iter().all(|s| DistanceAlg::Pythagoras.distance2d(p, *s) >= d));
+        }
+        assert_eq!(voronoi.get([15, 0]), None);
+
+        for row in voronoi.labels().cells.chunks(15) {
+            println!("{}", row.iter().map(|l| l.to_string()).collect::<String>());
+        }
+    }
+
+    #[test]
+    fn ties_go_to_first_seed() {
+        let voronoi = Voronoi::new([5, 1], [[0, 0], [4, 0]], DistanceAlg::Manhattan);
+        assert_eq!(voronoi.labels().cells, vec![0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn adjacency() {
+        // Four seeds in a row: each region only borders the ones beside it.
+        let seeds = [[2, 2], [7, 2], [12, 2], [17, 2]];
+        let voronoi = Voronoi::new([20, 5], seeds, DistanceAlg::Chebyshev);
+        assert_eq!(voronoi.neighbors(0), &[1]);
+        assert_eq!(voronoi.neighbors(1), &[0, 2]);
+        assert_eq!(voronoi.neighbors(3), &[2]);
+        assert!(voronoi.are_adjacent(2, 1));
+        assert!(!voronoi.are_adjacent(0, 3));
+        assert_eq!(voronoi.neighbors(9), &[] as &[u32]);
+        assert_eq!(voronoi.cells(0).count(), 25);
+    }
+
+    #[test]
+    fn no_seeds() {
+        let voronoi = Voronoi::new([4, 4], [] as [IVec2; 0], DistanceAlg::Pythagoras);
+        assert_eq!(voronoi.get([1, 1]), None);
+        assert_eq!(voronoi.region_count(), 0);
+    }
+}
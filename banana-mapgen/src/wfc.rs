@@ -0,0 +1,350 @@
+use banana_direction::prelude::*;
+use banana_grid::prelude::*;
+use rand::Rng;
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// Why a [`Wfc`] could not be built or could not generate a map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfcError {
+    /// The sample is smaller than a single pattern.
+    SampleTooSmall,
+    /// The requested map is smaller than a single pattern.
+    OutputTooSmall,
+    /// No pattern fits at this position of the map. Another seed may well succeed.
+    Contradiction(IVec2),
+}
+
+impl fmt::Display for WfcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WfcError::SampleTooSmall => write!(f, "the sample is smaller than a pattern"),
+            WfcError::OutputTooSmall => write!(f, "the output is smaller than a pattern"),
+            WfcError::Contradiction(p) => write!(f, "no pattern fits at {p}"),
+        }
+    }
+}
+
+impl std::error::Error for WfcError {}
+
+/// Wave Function Collapse, overlapping model, as described by Maxim Gumin.
+///
+/// Every `n`x`n` pattern of a sample map is collected, along with how often it appears.
+/// Generated maps are made only of those patterns, overlapping each other the same way
+/// they can in the sample, and appearing about as often.
+///
+/// See: [WaveFunctionCollapse](https://github.com/mxgmn/WaveFunctionCollapse)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wfc<T> {
+    n: i32,
+    /// Every distinct pattern, in row-major order.
+    patterns: Vec<Vec<T>>,
+    /// How often each pattern appears in the sample.
+    weights: Vec<f32>,
+    /// The patterns that may sit next to each pattern, in each direction.
+    adjacency: Vec<CardinalDirectionTable<Vec<usize>>>,
+}
+
+impl<T> Wfc<T>
+where
+    T: Copy + Eq + Hash,
+{
+    /// Learn the `n`x`n` patterns of `sample`. With `periodic`, patterns wrap around the
+    /// edges of the sample as if it were tiled.
+    pub fn new(sample: &Grid<T>, n: u32, periodic: bool) -> Result<Self, WfcError> {
+        let n = n.max(1) as i32;
+        let size = sample.size().as_ivec2();
+        if size.cmplt(IVec2::splat(n)).any() {
+            return Err(WfcError::SampleTooSmall);
+        }
+
+        let positions = if periodic { size } else { size - IVec2::splat(n - 1) };
+        let mut index: HashMap<Vec<T>, usize> = HashMap::new();
+        let mut patterns = Vec::new();
+        let mut weights = Vec::new();
+        for origin in positions.as_uvec2().iter() {
+            let pattern: Vec<T> = IVec2::splat(n)
+                .as_uvec2()
+                .iter()
+                .map(|offset| sample[(origin + offset) % size])
+                .collect();
+            let id = *index.entry(pattern).or_insert_with_key(|pattern| {
+                patterns.push(pattern.clone());
+                weights.push(0.0);
+                patterns.len() - 1
+            });
+            weights[id] += 1.0;
+        }
+
+        let adjacency = (0..patterns.len())
+            .map(|a| {
+                CardinalDirectionTable::new_fn(|direction| {
+                    (0..patterns.len())
+                        .filter(|b| agrees(&patterns[a], &patterns[*b], direction.coord(), n))
+                        .collect()
+                })
+            })
+            .collect();
+
+        Ok(Self { n, patterns, weights, adjacency })
+    }
+
+    /// How many distinct patterns were found in the sample.
+    #[inline]
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Generate a map, drawing every choice from `rng`.
+    ///
+    /// Fails with [`WfcError::Contradiction`] when the generator paints itself into a
+    /// corner, in which case trying again with another seed usually works.
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        size: impl Size2d,
+        rng: &mut R,
+    ) -> Result<Grid<T>, WfcError> {
+        let size = size.as_ivec2();
+        if size.cmplt(IVec2::splat(self.n)).any() {
+            return Err(WfcError::OutputTooSmall);
+        }
+
+        // Patterns with no neighbor in some direction are ruled out before anything is
+        // observed, so the first propagation starts from every cell.
+        let mut wave = Wave::new(self, size - IVec2::splat(self.n - 1));
+        wave.propagate(self, wave.size.as_uvec2().iter().collect())?;
+        while let Some(cell) = wave.lowest_entropy(rng) {
+            wave.observe(self, cell, rng);
+            wave.propagate(self, vec![cell])?;
+        }
+
+        // Each cell takes its value from the pattern at or up and left of it, so the
+        // patterns along the right and bottom edges are seen in full.
+        let last = wave.size - IVec2::ONE;
+        Ok(Grid::new_fn(size, |p| {
+            let cell = p.min(last);
+            let pattern = &self.patterns[wave.chosen(cell)];
+            let offset = p - cell;
+            pattern[(offset.y * self.n + offset.x) as usize]
+        }))
+    }
+}
+
+/// Whether pattern `b`, placed `offset` away from pattern `a`, agrees with it wherever
+/// they overlap.
+fn agrees<T: PartialEq>(a: &[T], b: &[T], offset: IVec2, n: i32) -> bool {
+    let min = offset.max(IVec2::ZERO);
+    let max = (offset + IVec2::splat(n)).min(IVec2::splat(n));
+    (min.y..max.y).all(|y| {
+        (min.x..max.x).all(|x| {
+            let (bx, by) = (x - offset.x, y - offset.y);
+            a[(y * n + x) as usize] == b[(by * n + bx) as usize]
+        })
+    })
+}
+
+/// Which patterns are still possible at every position of the output.
+struct Wave {
+    size: IVec2,
+    patterns: usize,
+    possible: Vec<bool>,
+    remaining: Vec<usize>,
+    /// Running sums of `w` and `w * ln(w)` over each cell's remaining patterns.
+    weight_sums: Vec<f32>,
+    weight_log_sums: Vec<f32>,
+}
+
+impl Wave {
+    fn new<T>(wfc: &Wfc<T>, size: IVec2) -> Self {
+        let cells = (size.x * size.y) as usize;
+        let patterns = wfc.patterns.len();
+        let weight_sum = wfc.weights.iter().sum::<f32>();
+        let weight_log_sum = wfc.weights.iter().map(|w| w * w.ln()).sum::<f32>();
+        Self {
+            size,
+            patterns,
+            possible: vec![true; cells * patterns],
+            remaining: vec![patterns; cells],
+            weight_sums: vec![weight_sum; cells],
+            weight_log_sums: vec![weight_log_sum; cells],
+        }
+    }
+
+    fn index(&self, cell: IVec2) -> usize {
+        (cell.y * self.size.x + cell.x) as usize
+    }
+
+    fn is_possible(&self, cell: usize, pattern: usize) -> bool {
+        self.possible[cell * self.patterns + pattern]
+    }
+
+    fn chosen(&self, cell: IVec2) -> usize {
+        let cell = self.index(cell);
+        (0..self.patterns).find(|p| self.is_possible(cell, *p)).unwrap_or_default()
+    }
+
+    fn ban<T>(&mut self, wfc: &Wfc<T>, cell: usize, pattern: usize) {
+        self.possible[cell * self.patterns + pattern] = false;
+        self.remaining[cell] -= 1;
+        let weight = wfc.weights[pattern];
+        self.weight_sums[cell] -= weight;
+        self.weight_log_sums[cell] -= weight * weight.ln();
+    }
+
+    /// The undecided cell with the fewest likely patterns left, with ties broken at
+    /// random. `None` once every cell is decided.
+    fn lowest_entropy<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<IVec2> {
+        let mut best = None;
+        let mut lowest = f32::INFINITY;
+        for cell in self.size.as_uvec2().iter() {
+            let i = self.index(cell);
+            if self.remaining[i] <= 1 {
+                continue;
+            }
+            let sum = self.weight_sums[i];
+            let entropy = sum.ln() - self.weight_log_sums[i] / sum + rng.gen::<f32>() * 1e-4;
+            if entropy < lowest {
+                lowest = entropy;
+                best = Some(cell);
+            }
+        }
+        best
+    }
+
+    /// Collapses a cell to one of its remaining patterns, picked by weight.
+    fn observe<T, R: Rng + ?Sized>(&mut self, wfc: &Wfc<T>, cell: IVec2, rng: &mut R) {
+        let i = self.index(cell);
+        let mut roll = rng.gen::<f32>() * self.weight_sums[i];
+        let mut chosen = None;
+        for pattern in (0..self.patterns).filter(|p| self.is_possible(i, *p)) {
+            chosen = Some(pattern);
+            roll -= wfc.weights[pattern];
+            if roll <= 0.0 {
+                break;
+            }
+        }
+
+        for pattern in 0..self.patterns {
+            if Some(pattern) != chosen && self.is_possible(i, pattern) {
+                self.ban(wfc, i, pattern);
+            }
+        }
+    }
+
+    /// Removes every pattern that no longer fits next to its neighbors, spreading out
+    /// from the cells in `stack`.
+    fn propagate<T>(&mut self, wfc: &Wfc<T>, mut stack: Vec<IVec2>) -> Result<(), WfcError> {
+        let mut allowed = vec![false; self.patterns];
+        while let Some(cell) = stack.pop() {
+            let i = self.index(cell);
+            for direction in CardinalDirections {
+                let neighbor = cell + direction.coord();
+                if neighbor.cmplt(IVec2::ZERO).any() || neighbor.cmpge(self.size).any() {
+                    continue;
+                }
+
+                allowed.fill(false);
+                for pattern in (0..self.patterns).filter(|p| self.is_possible(i, *p)) {
+                    for other in &wfc.adjacency[pattern][direction] {
+                        allowed[*other] = true;
+                    }
+                }
+
+                let j = self.index(neighbor);
+                let mut changed = false;
+                for (pattern, allowed) in allowed.iter().enumerate() {
+                    if !allowed && self.is_possible(j, pattern) {
+                        self.ban(wfc, j, pattern);
+                        changed = true;
+                    }
+                }
+                if self.remaining[j] == 0 {
+                    return Err(WfcError::Contradiction(neighbor));
+                }
+                if changed {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_grid::prelude::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    fn sample(text: &str) -> Grid<char> {
+        let rows: Vec<&str> = text.trim().lines().map(str::trim).collect();
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| rows[p.y as usize].as_bytes()[p.x as usize] as char)
+    }
+
+    fn windows(grid: &Grid<char>, n: i32) -> HashSet<Vec<char>> {
+        (grid.size().as_ivec2() - IVec2::splat(n - 1))
+            .as_uvec2()
+            .iter()
+            .map(|p| IVec2::splat(n).as_uvec2().iter().map(|o| grid[p + o]).collect())
+            .collect()
+    }
+
+    #[test]
+    fn patterns() {
+        let sample = sample("ab\ncd");
+        assert_eq!(Wfc::new(&sample, 2, false).unwrap().pattern_count(), 1);
+        assert_eq!(Wfc::new(&sample, 2, true).unwrap().pattern_count(), 4);
+        assert_eq!(Wfc::new(&sample, 3, false), Err(WfcError::SampleTooSmall));
+    }
+
+    #[test]
+    fn generates_from_patterns() {
+        let sample = sample(
+            "
+            ........
+            .###....
+            .#.#....
+            .###....
+            ......#.
+            ......#.
+            ....###.
+            ........
+        ",
+        );
+        let wfc = Wfc::new(&sample, 3, true).unwrap();
+        let known = windows(&sample, 3);
+
+        let map = (0..20)
+            .find_map(|seed| wfc.generate([30, 20], &mut StdRng::seed_from_u64(seed)).ok())
+            .expect("every seed ran into a contradiction");
+        assert_eq!(map.size(), UVec2::new(30, 20));
+
+        // Every window of the output appears in the (wrapped) sample.
+        let wrapped = Grid::new_fn([10, 10], |p| sample[p % 8]);
+        let known: HashSet<_> = known.union(&windows(&wrapped, 3)).cloned().collect();
+        assert!(windows(&map, 3).is_subset(&known));
+
+        for row in map.cells.chunks(30) {
+            println!("{}", row.iter().collect::<String>());
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        let sample = sample("..#.\n.##.\n....\n#..#");
+        let wfc = Wfc::new(&sample, 2, true).unwrap();
+        let a = wfc.generate([16, 16], &mut StdRng::seed_from_u64(11));
+        assert_eq!(a, wfc.generate([16, 16], &mut StdRng::seed_from_u64(11)));
+    }
+
+    #[test]
+    fn contradiction() {
+        // The single pattern can't sit next to a copy of itself in any direction.
+        let wfc = Wfc::new(&sample("ab\ncd"), 2, false).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(wfc.generate([2, 2], &mut rng).unwrap(), sample("ab\ncd"));
+        assert!(matches!(wfc.generate([3, 2], &mut rng), Err(WfcError::Contradiction(_))));
+        assert_eq!(wfc.generate([1, 5], &mut rng), Err(WfcError::OutputTooSmall));
+    }
+}
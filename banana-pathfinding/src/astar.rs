@@ -0,0 +1,247 @@
+use crate::prelude::*;
+use banana_direction::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A* search between two cells of a grid.
+///
+/// Every step costs the cost of the cell being entered times the length of the step as
+/// measured by `heuristic`, so the heuristic never overestimates as long as no cell
+/// costs less than `1.0`.
+///
+/// Paths stay on the grid: reading it with an `EdgeMode` doesn't let them step past its
+/// edge.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AStar {
+    /// The neighbors expanded from each cell.
+    pub movement: Neighborhood,
+    /// Estimates the remaining cost to the goal, and measures the length of each step.
+    pub heuristic: DistanceAlg,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        Self::new(Neighborhood::Cardinal, DistanceAlg::Manhattan)
+    }
+}
+
+impl AStar {
+    pub const fn new(movement: Neighborhood, heuristic: DistanceAlg) -> Self {
+        Self { movement, heuristic }
+    }
+
+    /// Find the cheapest path from `start` to `goal`.
+    ///
+    /// `cost` returns the cost of entering a cell, or `None` if the cell can't be
+    /// entered. The start cell is never costed. Returns `None` if there is no path.
+    pub fn find_path<T, G, F>(
+        &self,
+        grid: &G,
+        start: impl GridPoint,
+        goal: impl GridPoint,
+        cost: F,
+    ) -> Option<Path>
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> Option<f32>,
+    {
+        let (start, goal) = (start.as_ivec2(), goal.as_ivec2());
+        if !grid.in_bounds(start) || grid.get(goal).and_then(&cost).is_none() {
+            return None;
+        }
+
+        let mut best = Grid::new(grid.size(), f32::INFINITY);
+        let mut came_from: Grid<Option<Direction>> = Grid::new(grid.size(), None);
+        let mut open = BinaryHeap::new();
+
+        best[start] = 0.0;
+        open.push(Node {
+            estimate: self.heuristic.distance2d(start, goal),
+            cost: 0.0,
+            point: start,
+        });
+
+        while let Some(Node { cost: so_far, point, .. }) = open.pop() {
+            if point == goal {
+                return Some(Path { steps: walk_back(&came_from, goal), cost: so_far });
+            }
+            // A cheaper route to this cell was found after this entry was queued.
+            if so_far > best[point] {
+                continue;
+            }
+
+            for direction in self.movement.directions() {
+                let next = point + direction.coord();
+                if !grid.in_bounds(next) {
+                    continue;
+                }
+                let Some(step) = grid.get(next).and_then(&cost) else { continue };
+
+                let through = so_far + step * self.heuristic.distance2d(point, next);
+                if through < best[next] {
+                    best[next] = through;
+                    came_from[next] = Some(direction);
+                    open.push(Node {
+                        estimate: through + self.heuristic.distance2d(next, goal),
+                        cost: through,
+                        point: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Follows the recorded directions back from `goal` to the cell with none.
+pub(crate) fn walk_back(came_from: &Grid<Option<Direction>>, goal: IVec2) -> Vec<IVec2> {
+    let mut steps = vec![goal];
+    let mut current = goal;
+    while let Some(direction) = came_from[current] {
+        current -= direction.coord();
+        steps.push(current);
+    }
+    steps.reverse();
+    steps
+}
+
+/// An entry in the open set, ordered so the lowest estimate is popped first.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Node {
+    pub(crate) estimate: f32,
+    pub(crate) cost: f32,
+    pub(crate) point: IVec2,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed for the max-heap, preferring the node furthest along on ties.
+        other.estimate.total_cmp(&self.estimate).then(self.cost.total_cmp(&other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::Neighborhood;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+    use banana_utils::Canvas;
+
+    fn walls(rows: &[&str]) -> Grid<bool> {
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| rows[p.y as usize].as_bytes()[p.x as usize] == b'#')
+    }
+
+    fn floor(wall: &bool) -> Option<f32> {
+        (!wall).then_some(1.0)
+    }
+
+    #[test]
+    fn open_grid() {
+        let grid = Grid::new([10, 10], false);
+        let path = AStar::default().find_path(&grid, [1, 1], [7, 4], floor).unwrap();
+
+        assert_eq!(path.start(), Some(IVec2::new(1, 1)));
+        assert_eq!(path.goal(), Some(IVec2::new(7, 4)));
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.cost, 9.0);
+        assert!(path.steps.windows(2).all(|w| (w[1] - w[0]).abs().max_element() == 1));
+    }
+
+    #[test]
+    fn diagonal() {
+        let grid = Grid::new([10, 10], false);
+        let astar = AStar::new(Neighborhood::All, DistanceAlg::Chebyshev);
+        let path = astar.find_path(&grid, [1, 1], [7, 4], floor).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.cost, 6.0);
+
+        let astar = AStar::new(Neighborhood::All, DistanceAlg::Pythagoras);
+        let path = astar.find_path(&grid, [0, 0], [3, 3], floor).unwrap();
+        assert_eq!(path.len(), 3);
+        assert!((path.cost - 3.0 * std::f32::consts::SQRT_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn around_walls() {
+        let grid = walls(&[
+            "..........", //
+            ".#######..",
+            ".#.....#..",
+            ".#.###.#..",
+            "...#...#..",
+            "####.###..",
+        ]);
+        let path = AStar::default().find_path(&grid, [4, 5], [9, 0], floor).unwrap();
+        assert!(path.steps.iter().all(|p| !grid[*p]));
+        assert_eq!(path.len(), 26);
+
+        let mut canvas = Canvas::new(grid.size());
+        for p in grid.size().iter().filter(|p| grid[*p]) {
+            canvas.put(p, '#');
+        }
+        for p in &path.steps {
+            canvas.put(*p, '*');
+        }
+        canvas.print();
+    }
+
+    #[test]
+    fn weighted() {
+        // Wading through the water is shorter but costs more than walking around.
+        let grid = Grid::new_fn([7, 3], |p| p.x == 3 && p.y < 2);
+        let path = AStar::default()
+            .find_path(&grid, [0, 0], [6, 0], |water| Some(if *water { 10.0 } else { 1.0 }))
+            .unwrap();
+        assert!(path.steps.contains(&IVec2::new(3, 2)));
+        assert_eq!(path.cost, 10.0);
+    }
+
+    #[test]
+    fn no_path() {
+        let grid = walls(&[
+            "..#..", //
+            "..#..", "..#..",
+        ]);
+        assert!(AStar::default().find_path(&grid, [0, 0], [4, 0], floor).is_none());
+        assert!(AStar::default().find_path(&grid, [0, 0], [2, 0], floor).is_none());
+        assert!(AStar::default().find_path(&grid, [0, 0], [9, 9], floor).is_none());
+
+        let path = AStar::default().find_path(&grid, [1, 1], [1, 1], floor).unwrap();
+        assert!(path.is_empty());
+        assert_eq!(path.cost, 0.0);
+    }
+
+    #[test]
+    fn edge_modes() {
+        // Paths never step past the edge, whatever the grid reads there.
+        let grid = Grid::new([5, 3], false);
+        for edges in [EdgeMode::Wrap, EdgeMode::Constant(false)] {
+            let view = grid.with_edges(edges);
+            let path = AStar::default().find_path(&view, [0, 1], [4, 1], floor).unwrap();
+            assert_eq!(path.len(), 4);
+            assert!(path.steps.iter().all(|p| grid.in_bounds(*p)));
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use crate::astar::Node;
+use banana_direction::prelude::*;
+use banana_grid::prelude::*;
+use std::collections::BinaryHeap;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// The distance from every cell to the nearest of a set of goals.
+///
+/// Each step costs `1.0`, diagonals included, and goals start at their own weight so
+/// that some goals can be made more attractive than others. Rolling down the map from
+/// any cell leads to a goal, and rolling down a [flee map](Self::flee) leads away from
+/// them.
+///
+/// See: [The Incredible Power of Dijkstra Maps](http://www.roguebasin.com/index.php/The_Incredible_Power_of_Dijkstra_Maps)
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DijkstraMap {
+    /// The neighbors expanded from each cell, and checked when rolling down.
+    pub movement: Neighborhood,
+    /// Cells further than this from every goal are left unreachable.
+    pub max_distance: f32,
+    /// The distance of every cell, [`f32::INFINITY`] where unreachable.
+    map: Grid<f32>,
+}
+
+impl DijkstraMap {
+    /// Create a map of the given size where every cell is unreachable.
+    pub fn new(size: impl Size2d, movement: Neighborhood, max_distance: f32) -> Self {
+        Self { movement, max_distance, map: Grid::new(size, f32::INFINITY) }
+    }
+
+    /// Create a map and [build](Self::build) it from `goals`.
+    pub fn new_goals<T, G, F>(
+        grid: &G,
+        goals: impl IntoIterator<Item = (IVec2, f32)>,
+        movement: Neighborhood,
+        max_distance: f32,
+        is_passable: F,
+    ) -> Self
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        let mut map = Self::new(grid.size(), movement, max_distance);
+        map.build(grid, goals, is_passable);
+        map
+    }
+
+    /// Recompute every distance from weighted `goals`, only passing through cells for
+    /// which `is_passable` returns true.
+    ///
+    /// Goals outside the grid or on impassable cells are ignored.
+    pub fn build<T, G, F>(
+        &mut self,
+        grid: &G,
+        goals: impl IntoIterator<Item = (IVec2, f32)>,
+        is_passable: F,
+    ) where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        self.map = Grid::new(grid.size(), f32::INFINITY);
+        let seeds = goals
+            .into_iter()
+            .filter(|(p, _)| grid.in_bounds(*p) && grid.get(*p).is_some_and(&is_passable));
+        self.relax(seeds, |p| grid.get(p).is_some_and(&is_passable));
+    }
+
+    /// Create a map that leads away from the goals of this one.
+    ///
+    /// Every reachable distance is multiplied by `-multiplier` and the map is then
+    /// rebuilt from those values, so fleeing from a goal prefers open space over running
+    /// into a corner. A multiplier of around `1.2` works well. Cells unreachable in this
+    /// map stay unreachable.
+    pub fn flee(&self, multiplier: f32) -> Self {
+        let seeds = self
+            .map
+            .size()
+            .iter()
+            .filter(|p| self.map[*p].is_finite())
+            .map(|p| (p, self.map[p] * -multiplier))
+            .collect::<Vec<_>>();
+
+        let mut flee = Self::new(self.size(), self.movement, f32::INFINITY);
+        flee.relax(seeds, |p| self.get(p).is_some());
+        flee
+    }
+
+    /// Dijkstra's algorithm from every seed at once.
+    fn relax(
+        &mut self,
+        seeds: impl IntoIterator<Item = (IVec2, f32)>,
+        passable: impl Fn(IVec2) -> bool,
+    ) {
+        let mut open = BinaryHeap::new();
+        for (point, weight) in seeds {
+            if weight < self.map[point] {
+                self.map[point] = weight;
+                open.push(Node { estimate: weight, cost: weight, point });
+            }
+        }
+
+        while let Some(Node { cost, point, .. }) = open.pop() {
+            if cost > self.map[point] {
+                continue;
+            }
+
+            let through = cost + 1.0;
+            if through > self.max_distance {
+                continue;
+            }
+            for direction in self.movement.directions() {
+                let next = point + direction.coord();
+                if self.map.in_bounds(next) && through < self.map[next] && passable(next) {
+                    self.map[next] = through;
+                    open.push(Node { estimate: through, cost: through, point: next });
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.map.size()
+    }
+
+    /// The distance of a cell, or `None` if it is unreachable or outside the map.
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<f32> {
+        self.map.get(point).copied().filter(|d| d.is_finite())
+    }
+
+    /// The underlying grid of distances, [`f32::INFINITY`] where unreachable.
+    #[inline]
+    pub fn grid(&self) -> &Grid<f32> {
+        &self.map
+    }
+
+    /// The distance of every neighbor allowed by the map's movement.
+    pub fn neighbors(&self, point: impl GridPoint) -> DirectionTable<Option<f32>> {
+        let point = point.as_ivec2();
+        let mut table = DirectionTable::new_clone(None);
+        for direction in self.movement.directions() {
+            table[direction] = self.get(point + direction.coord());
+        }
+        table
+    }
+
+    /// The direction of the lowest neighbor, if any is lower than `point` itself.
+    ///
+    /// Ties are broken in [`Direction`] order.
+    pub fn rolldown(&self, point: impl GridPoint) -> Option<Direction> {
+        let mut lowest = self.get(point)?;
+        let mut best = None;
+        for (direction, distance) in self.neighbors(point).enumerate() {
+            if let Some(distance) = *distance {
+                if distance < lowest {
+                    lowest = distance;
+                    best = Some(direction);
+                }
+            }
+        }
+        best
+    }
+
+    /// Follows [`rolldown`](Self::rolldown) from `point` until it reaches a lowest cell,
+    /// returning every cell visited along the way.
+    pub fn rolldown_path(&self, point: impl GridPoint) -> Vec<IVec2> {
+        let mut current = point.as_ivec2();
+        let mut steps = vec![current];
+        while let Some(direction) = self.rolldown(current) {
+            current += direction.coord();
+            steps.push(current);
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::*;
+    use banana_grid::prelude::*;
+
+    fn walls(rows: &[&str]) -> Grid<bool> {
+        let size = UVec2::new(rows[0].len() as u32, rows.len() as u32);
+        Grid::new_fn(size, |p| rows[p.y as usize].as_bytes()[p.x as usize] == b'#')
+    }
+
+    #[test]
+    fn distances() {
+        let grid = walls(&[
+            ".....", //
+            ".###.", ".....",
+        ]);
+        let map = DijkstraMap::new_goals(
+            &grid,
+            [(IVec2::new(0, 1), 0.0)],
+            Neighborhood::Cardinal,
+            f32::INFINITY,
+            |wall| !wall,
+        );
+        assert_eq!(map.get([0, 1]), Some(0.0));
+        assert_eq!(map.get([2, 0]), Some(3.0));
+        assert_eq!(map.get([4, 1]), Some(6.0));
+        assert_eq!(map.get([2, 1]), None);
+        assert_eq!(map.get([9, 9]), None);
+    }
+
+    #[test]
+    fn weighted_goals() {
+        let grid = Grid::new([11, 1], false);
+        let goals = [(IVec2::new(0, 0), 0.0), (IVec2::new(10, 0), -4.0)];
+        let map =
+            DijkstraMap::new_goals(&grid, goals, Neighborhood::Cardinal, f32::INFINITY, |w| !w);
+
+        // The right goal is more attractive, so it pulls cells past the middle.
+        assert_eq!(map.rolldown([3, 0]), Some(Direction::East));
+        assert_eq!(map.rolldown([2, 0]), Some(Direction::West));
+        assert_eq!(map.get([0, 0]), Some(0.0));
+        assert_eq!(map.get([5, 0]), Some(1.0));
+    }
+
+    #[test]
+    fn goals_off_the_grid() {
+        let grid = Grid::new([5, 5], false);
+        let goals = [(IVec2::new(-1, 0), 0.0), (IVec2::new(7, 1), 0.0), (IVec2::new(4, 4), 0.0)];
+        for edges in [EdgeMode::Wrap, EdgeMode::Clamp] {
+            let view = grid.with_edges(edges);
+            let map = DijkstraMap::new_goals(&view, goals, Neighborhood::Cardinal, 100.0, |w| !w);
+            assert_eq!(map.get([4, 4]), Some(0.0));
+            assert_eq!(map.get([0, 0]), Some(8.0));
+            assert_eq!(map.get([2, 1]), Some(5.0));
+        }
+    }
+
+    #[test]
+    fn max_distance() {
+        let grid = Grid::new([10, 10], false);
+        let map =
+            DijkstraMap::new_goals(&grid, [(IVec2::new(5, 5), 0.0)], Neighborhood::All, 3.0, |w| {
+                !w
+            });
+        assert_eq!(map.get([8, 8]), Some(3.0));
+        assert_eq!(map.get([9, 5]), None);
+        assert_eq!(map.grid().cells.iter().filter(|d| d.is_finite()).count(), 49);
+    }
+
+    #[test]
+    fn rolldown() {
+        let grid = walls(&[
+            "#######", //
+            "#.....#", "#.###.#", "#.#...#", "#######",
+        ]);
+        let map = DijkstraMap::new_goals(
+            &grid,
+            [(IVec2::new(3, 3), 0.0)],
+            Neighborhood::All,
+            f32::INFINITY,
+            |wall| !wall,
+        );
+        let path = map.rolldown_path([1, 3]);
+        assert_eq!(path.first(), Some(&IVec2::new(1, 3)));
+        assert_eq!(path.last(), Some(&IVec2::new(3, 3)));
+        assert_eq!(path.len(), 8);
+        assert!(path.iter().all(|p| !grid[*p]));
+        assert_eq!(map.rolldown([3, 3]), None);
+    }
+
+    #[test]
+    fn flee() {
+        // A dead end to the left of the goal and a room to the right.
+        let grid = walls(&[
+            "#####....", //
+            ".........",
+            "#####....",
+        ]);
+        let map = DijkstraMap::new_goals(
+            &grid,
+            [(IVec2::new(2, 1), 0.0)],
+            Neighborhood::All,
+            f32::INFINITY,
+            |wall| !wall,
+        );
+        let flee = map.flee(1.2);
+
+        assert_eq!(map.rolldown([3, 1]), Some(Direction::West));
+        assert_eq!(flee.rolldown([3, 1]), Some(Direction::East));
+        // Both ways are one step from the goal, but only the room leads anywhere.
+        assert_eq!(flee.rolldown([2, 1]), Some(Direction::East));
+        assert!(flee.rolldown_path([2, 1]).last().unwrap().x == 8);
+        assert!(flee.get([2, 1]).unwrap() > flee.get([8, 1]).unwrap());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::astar::Node;
+use crate::prelude::*;
+use banana_direction::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+use std::collections::BinaryHeap;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// The direction of the cheapest step towards the nearest goal, for every cell at once.
+///
+/// Built once from a cost grid and then shared by any number of units heading to the
+/// same goals, which only have to look up the cell they stand on.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowField {
+    movement: Neighborhood,
+    /// The cheapest cost from every cell to a goal, [`f32::INFINITY`] where unreachable.
+    costs: Grid<f32>,
+    /// The step to take from every cell, `None` at the goals and unreachable cells.
+    flow: Grid<Option<Direction>>,
+}
+
+impl FlowField {
+    /// Build a flow field leading to the nearest of `goals`.
+    ///
+    /// `cost` returns the cost of entering a cell, or `None` if the cell can't be
+    /// entered. Like with [`AStar`], each step costs the cell's cost times its length.
+    pub fn new<T, G, F>(
+        grid: &G,
+        goals: impl IntoIterator<Item = impl GridPoint>,
+        movement: Neighborhood,
+        cost: F,
+    ) -> Self
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> Option<f32>,
+    {
+        let mut field = Self {
+            movement,
+            costs: Grid::new(grid.size(), f32::INFINITY),
+            flow: Grid::new(grid.size(), None),
+        };
+
+        let mut open = BinaryHeap::new();
+        for goal in goals {
+            let goal = goal.as_ivec2();
+            if grid.in_bounds(goal) && grid.get(goal).and_then(&cost).is_some() {
+                field.costs[goal] = 0.0;
+                open.push(Node { estimate: 0.0, cost: 0.0, point: goal });
+            }
+        }
+
+        // Search outwards from the goals, so every step is taken against the flow.
+        while let Some(Node { cost: so_far, point, .. }) = open.pop() {
+            if so_far > field.costs[point] {
+                continue;
+            }
+            let Some(step) = grid.get(point).and_then(&cost) else { continue };
+
+            for direction in movement.directions() {
+                let next = point + direction.coord();
+                if !grid.in_bounds(next) || grid.get(next).and_then(&cost).is_none() {
+                    continue;
+                }
+
+                let length = DistanceAlg::Pythagoras.distance2d(point, next);
+                let through = so_far + step * length;
+                if through < field.costs[next] {
+                    field.costs[next] = through;
+                    field.flow[next] = Some(direction.opposite());
+                    open.push(Node { estimate: through, cost: through, point: next });
+                }
+            }
+        }
+
+        field
+    }
+
+    #[inline]
+    pub fn size(&self) -> UVec2 {
+        self.flow.size()
+    }
+
+    #[inline]
+    pub fn movement(&self) -> Neighborhood {
+        self.movement
+    }
+
+    /// The direction to step in from a cell, or `None` at a goal, an unreachable cell or
+    /// outside the field.
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<Direction> {
+        self.flow.get(point).copied().flatten()
+    }
+
+    /// The cost left to reach the nearest goal from a cell, or `None` if it can't.
+    #[inline]
+    pub fn cost(&self, point: impl GridPoint) -> Option<f32> {
+        self.costs.get(point).copied().filter(|c| c.is_finite())
+    }
+
+    /// Whether a cell is one of the goals the field leads to.
+    ///
+    /// Cells that cost nothing to cross can be reached for free, so goals are told apart
+    /// by having no step to take rather than by their cost.
+    #[inline]
+    pub fn is_goal(&self, point: impl GridPoint) -> bool {
+        self.cost(point).is_some() && self.get(point).is_none()
+    }
+
+    #[inline]
+    pub fn grid(&self) -> &Grid<Option<Direction>> {
+        &self.flow
+    }
+
+    /// Every direction from each cell that leads to a cheaper one, for spreading a
+    /// crowd out over equally good steps instead of lining it up.
+    pub fn to_bitmap_grid(&self) -> Grid<DirectionBitmap> {
+        Grid::new_fn(self.size(), |point| {
+            let mut bitmap = DirectionBitmap::empty();
+            let Some(here) = self.cost(point) else { return bitmap };
+            for direction in self.movement.directions() {
+                if self.cost(point + direction.coord()).is_some_and(|c| c < here) {
+                    bitmap |= direction.bitmap();
+                }
+            }
+            bitmap
+        })
+    }
+
+    /// Follows the flow from `point` until it reaches a goal, returning every cell
+    /// visited along the way, or `None` if no goal can be reached from it.
+    pub fn path(&self, point: impl GridPoint) -> Option<Path> {
+        let mut current = point.as_ivec2();
+        let cost = self.cost(current)?;
+        let mut steps = vec![current];
+        while let Some(direction) = self.get(current) {
+            current += direction.coord();
+            steps.push(current);
+        }
+        Some(Path { steps, cost })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn floor(wall: &bool) -> Option<f32> {
+        (!wall).then_some(1.0)
+    }
+
+    #[test]
+    fn single_goal() {
+        let grid = Grid::new([7, 7], false);
+        let field = FlowField::new(&grid, [IVec2::new(3, 3)], Neighborhood::All, floor);
+
+        assert!(field.is_goal([3, 3]));
+        assert_eq!(field.get([3, 3]), None);
+        assert_eq!(field.get([0, 3]), Some(Direction::East));
+        assert_eq!(field.get([3, 6]), Some(Direction::North));
+        assert_eq!(field.get([0, 0]), Some(Direction::SouthEast));
+        assert_eq!(field.cost([3, 0]), Some(3.0));
+    }
+
+    #[test]
+    fn nearest_goal() {
+        let grid = Grid::new([9, 1], false);
+        let goals = [IVec2::new(0, 0), IVec2::new(8, 0)];
+        let field = FlowField::new(&grid, goals, Neighborhood::Cardinal, floor);
+
+        assert_eq!(field.get([3, 0]), Some(Direction::West));
+        assert_eq!(field.get([5, 0]), Some(Direction::East));
+        assert_eq!(field.path([6, 0]).unwrap().goal(), Some(IVec2::new(8, 0)));
+    }
+
+    #[test]
+    fn free_cells() {
+        // A road leading away from the goal costs nothing to travel.
+        let grid = Grid::new_fn([5, 1], |p| p.x <= 1);
+        let field = FlowField::new(&grid, [IVec2::new(0, 0)], Neighborhood::Cardinal, |road| {
+            Some(if *road { 0.0 } else { 1.0 })
+        });
+
+        assert!(field.is_goal([0, 0]));
+        assert_eq!(field.cost([1, 0]), Some(0.0));
+        assert!(!field.is_goal([1, 0]));
+        assert_eq!(field.get([1, 0]), Some(Direction::West));
+        assert_eq!(field.path([3, 0]).unwrap().goal(), Some(IVec2::new(0, 0)));
+    }
+
+    #[test]
+    fn edge_modes() {
+        // The flow never leads past the edge, whatever the grid reads there.
+        let grid = Grid::new([5, 3], false);
+        for edges in [EdgeMode::Wrap, EdgeMode::Constant(false)] {
+            let view = grid.with_edges(edges);
+            let field = FlowField::new(&view, [IVec2::new(4, 1)], Neighborhood::Cardinal, floor);
+            assert_eq!(field.get([0, 1]), Some(Direction::East));
+            assert_eq!(field.cost([0, 1]), Some(4.0));
+        }
+    }
+
+    #[test]
+    fn matches_astar() {
+        let grid = Grid::new_fn([30, 20], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let goal = IVec2::new(15, 9);
+        let field = FlowField::new(&grid, [goal], Neighborhood::All, floor);
+        let astar = AStar::new(Neighborhood::All, DistanceAlg::Pythagoras);
+
+        for start in grid.size().iter().filter(|p| !grid[*p]) {
+            let path = field.path(start);
+            let expected = astar.find_path(&grid, start, goal, floor);
+            assert_eq!(path.is_some(), expected.is_some(), "{start}");
+            if let (Some(path), Some(expected)) = (path, expected) {
+                assert_eq!(path.goal(), Some(goal));
+                assert!(path.steps.iter().all(|p| !grid[*p]));
+                assert!((path.cost - expected.cost).abs() < 1e-3, "{start}");
+            }
+        }
+    }
+
+    #[test]
+    fn bitmap() {
+        let grid = Grid::new([5, 5], false);
+        let field = FlowField::new(&grid, [IVec2::new(2, 2)], Neighborhood::Cardinal, floor);
+        let bitmaps = field.to_bitmap_grid();
+
+        let corner = bitmaps[IVec2::new(0, 0)];
+        assert!(corner.has(Direction::East) && corner.has(Direction::South));
+        assert!(!corner.has(Direction::SouthEast));
+        assert!(bitmaps[IVec2::new(2, 2)].is_empty());
+        assert_eq!(bitmaps[IVec2::new(2, 0)], Direction::South.bitmap());
+    }
+}
@@ -0,0 +1,693 @@
+use crate::astar::Node;
+use crate::prelude::*;
+use banana_direction::prelude::*;
+use banana_geometry::prelude::{DistanceAlg, Rect};
+use banana_grid::prelude::*;
+use std::{
+    collections::{BinaryHeap, HashMap},
+    ops::{Index, IndexMut},
+};
+
+/// Entrances this long or longer get a transition at each end instead of one in the
+/// middle.
+const MAX_SINGLE_ENTRANCE: usize = 6;
+
+/// Hierarchical pathfinding (HPA*), as described by Adi Botea, Martin Müller and
+/// Jonathan Schaeffer.
+///
+/// Owns a [`Grid`] split into rectangular clusters. The entrances between neighboring
+/// clusters and the costs between entrances inside each cluster are worked out up
+/// front, so a path only has to be searched for over the entrances and then refined
+/// within the clusters it passes through.
+///
+/// Cells changed through [`get_mut`](Self::get_mut) or [`IndexMut`] mark their cluster
+/// as dirty, and only dirty clusters and their neighbors are rebuilt before the next
+/// search. Paths are close to, but not always, the shortest.
+///
+/// Any [`Neighborhood`] can be used, including custom ones that only allow stepping one
+/// way. Clusters that only touch at a corner are linked when diagonal steps are allowed.
+///
+/// See: [Near Optimal Hierarchical Path-Finding](https://webdocs.cs.ualberta.ca/~mmueller/ps/hpastar.pdf)
+pub struct HierarchicalPathfinder<T, F> {
+    grid: Grid<T>,
+    is_passable: F,
+    movement: Neighborhood,
+    cluster_size: IVec2,
+    /// How many clusters there are along each axis.
+    cluster_count: IVec2,
+    clusters: Vec<Cluster>,
+    dirty: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Cluster {
+    rect: Rect,
+    /// Entrance cells along each side and at each corner, facing the cluster that way.
+    entrances: DirectionTable<Vec<IVec2>>,
+    /// The cost from every entrance to the other entrances it can reach in the cluster.
+    edges: HashMap<IVec2, Vec<(IVec2, f32)>>,
+    dirty: bool,
+}
+
+impl<T, F> HierarchicalPathfinder<T, F>
+where
+    F: Fn(&T) -> bool,
+{
+    /// Split `grid` into clusters of `cluster_size` cells and build the abstract graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either side of `cluster_size` is zero.
+    pub fn new(
+        grid: Grid<T>,
+        cluster_size: impl Size2d,
+        movement: Neighborhood,
+        is_passable: F,
+    ) -> Self {
+        let cluster_size = cluster_size.as_ivec2();
+        assert!(cluster_size.cmpgt(IVec2::ZERO).all(), "clusters must not be empty");
+
+        let size = grid.size().as_ivec2();
+        let cluster_count = (size + cluster_size - IVec2::ONE) / cluster_size;
+        let clusters = cluster_count
+            .as_uvec2()
+            .iter()
+            .map(|c| {
+                let min = c * cluster_size;
+                Cluster {
+                    rect: Rect::from_corners(min, (min + cluster_size).min(size)),
+                    entrances: DirectionTable::new_default(),
+                    edges: HashMap::new(),
+                    dirty: true,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let dirty = (0..clusters.len()).collect();
+        let mut pathfinder =
+            Self { grid, is_passable, movement, cluster_size, cluster_count, clusters, dirty };
+        pathfinder.refresh();
+        pathfinder
+    }
+
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Consumes the pathfinder, returning the grid.
+    #[inline]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    #[inline]
+    pub fn movement(&self) -> Neighborhood {
+        self.movement
+    }
+
+    #[inline]
+    pub fn get(&self, point: impl GridPoint) -> Option<&T> {
+        self.grid.get(point)
+    }
+
+    /// Gets a cell to change, marking its cluster to be rebuilt before the next search.
+    pub fn get_mut(&mut self, point: impl GridPoint) -> Option<&mut T> {
+        let point = point.as_ivec2();
+        if let Some(cluster) = self.cluster_index(point) {
+            self.mark_dirty(cluster);
+        }
+        self.grid.get_mut(point)
+    }
+
+    /// The area covered by every cluster.
+    pub fn clusters(&self) -> impl Iterator<Item = Rect> + '_ {
+        self.clusters.iter().map(|cluster| cluster.rect)
+    }
+
+    /// Every entrance cell of the cluster covering `point`.
+    pub fn entrances(&self, point: impl GridPoint) -> Vec<IVec2> {
+        self.cluster_index(point.as_ivec2())
+            .map(|c| self.clusters[c].entrances.iter().flatten().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether any cluster changed since it was last built.
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Rebuild the entrances and edges of every dirty cluster and its neighbors.
+    ///
+    /// This happens on its own before each search.
+    pub fn refresh(&mut self) {
+        if self.dirty.is_empty() {
+            return;
+        }
+
+        let mut rebuild = Vec::new();
+        for cluster in std::mem::take(&mut self.dirty) {
+            self.clusters[cluster].dirty = false;
+            rebuild.push(cluster);
+            for direction in Direction::all() {
+                if let Some(neighbor) = self.neighbor(cluster, direction) {
+                    self.build_border(cluster, neighbor, direction);
+                    rebuild.push(neighbor);
+                }
+            }
+        }
+
+        rebuild.sort_unstable();
+        rebuild.dedup();
+        for cluster in rebuild {
+            self.build_edges(cluster);
+        }
+    }
+
+    /// Find a path from `start` to `goal`, rebuilding any dirty clusters first.
+    ///
+    /// Returns `None` if either end is impassable or there is no path.
+    pub fn find_path(&mut self, start: impl GridPoint, goal: impl GridPoint) -> Option<Path> {
+        self.refresh();
+
+        let (start, goal) = (start.as_ivec2(), goal.as_ivec2());
+        if !self.passable(start) || !self.passable(goal) {
+            return None;
+        }
+        if start == goal {
+            return Some(Path { steps: vec![start], cost: 0.0 });
+        }
+
+        let start_cluster = self.cluster_index(start)?;
+        let goal_cluster = self.cluster_index(goal)?;
+        let from_start = self.search(start_cluster, start, false);
+        if start_cluster == goal_cluster {
+            if let Some(cost) = from_start.cost(goal) {
+                return Some(Path { steps: from_start.path(goal), cost });
+            }
+        }
+        let to_goal = self.search(goal_cluster, goal, true);
+
+        let route = self.search_abstract(start, goal, &from_start, &to_goal)?;
+        Some(self.refine(&route, &from_start, &to_goal))
+    }
+
+    /// A* over the entrances, with the start and goal linked to their own cluster.
+    fn search_abstract(
+        &self,
+        start: IVec2,
+        goal: IVec2,
+        from_start: &LocalSearch,
+        to_goal: &LocalSearch,
+    ) -> Option<Vec<IVec2>> {
+        let mut best = HashMap::from([(start, 0.0)]);
+        let mut parents: HashMap<IVec2, IVec2> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        open.push(Node { estimate: distance(start, goal), cost: 0.0, point: start });
+
+        while let Some(Node { cost: so_far, point, .. }) = open.pop() {
+            if point == goal {
+                let mut route = vec![goal];
+                let mut current = goal;
+                while let Some(parent) = parents.get(&current) {
+                    current = *parent;
+                    route.push(current);
+                }
+                route.reverse();
+                return Some(route);
+            }
+            if best.get(&point).is_some_and(|b| so_far > *b) {
+                continue;
+            }
+
+            let mut edges = if point == start {
+                self.entrances(start)
+                    .into_iter()
+                    .filter_map(|e| from_start.cost(e).map(|cost| (e, cost)))
+                    .collect()
+            } else {
+                self.cluster_index(point)
+                    .and_then(|c| self.clusters[c].edges.get(&point))
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            edges.extend(self.crossings(point));
+            if let Some(cost) = to_goal.cost(point) {
+                edges.push((goal, cost));
+            }
+
+            for (next, cost) in edges {
+                let through = so_far + cost;
+                if best.get(&next).is_none_or(|b| through < *b) {
+                    best.insert(next, through);
+                    parents.insert(next, point);
+                    open.push(Node {
+                        estimate: through + distance(next, goal),
+                        cost: through,
+                        point: next,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Expands a route over entrances into every cell along the way.
+    fn refine(&self, route: &[IVec2], from_start: &LocalSearch, to_goal: &LocalSearch) -> Path {
+        let last = route.len() - 1;
+        let mut steps = vec![route[0]];
+        let mut cost = 0.0;
+
+        for (i, pair) in route.windows(2).enumerate() {
+            let (from, to) = (pair[0], pair[1]);
+            if self.cluster_index(from) != self.cluster_index(to) {
+                steps.push(to);
+                cost += distance(from, to);
+                continue;
+            }
+
+            // The start and goal searches already cover their own legs.
+            let (leg, leg_cost) = if i == 0 {
+                (from_start.path(to), from_start.cost(to))
+            } else if i + 1 == last {
+                let mut leg = to_goal.path(from);
+                leg.reverse();
+                (leg, to_goal.cost(from))
+            } else {
+                let search = self.search(self.cluster_index(from).unwrap_or_default(), from, false);
+                (search.path(to), search.cost(to))
+            };
+            steps.extend(leg.into_iter().skip(1));
+            cost += leg_cost.unwrap_or_default();
+        }
+
+        Path { steps, cost }
+    }
+
+    fn passable(&self, point: IVec2) -> bool {
+        self.grid.get(point).is_some_and(&self.is_passable)
+    }
+
+    fn cluster_index(&self, point: IVec2) -> Option<usize> {
+        if !self.grid.in_bounds(point) {
+            return None;
+        }
+        let cluster = point / self.cluster_size;
+        Some((cluster.y * self.cluster_count.x + cluster.x) as usize)
+    }
+
+    fn neighbor(&self, cluster: usize, direction: Direction) -> Option<usize> {
+        let rect = self.clusters[cluster].rect;
+        let beyond = |offset: i32, min: i32, max: i32| match offset {
+            -1 => min - 1,
+            0 => min,
+            _ => max,
+        };
+        let offset = direction.coord();
+        self.cluster_index(IVec2::new(
+            beyond(offset.x, rect.min.x, rect.max.x),
+            beyond(offset.y, rect.min.y, rect.max.y),
+        ))
+    }
+
+    fn mark_dirty(&mut self, cluster: usize) {
+        if !self.clusters[cluster].dirty {
+            self.clusters[cluster].dirty = true;
+            self.dirty.push(cluster);
+        }
+    }
+
+    /// The entrances across a border that can be stepped to from `point`, with the cost
+    /// of the step.
+    fn crossings(&self, point: IVec2) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+        let cluster = self.cluster_index(point);
+        self.movement.directions().filter_map(move |direction| {
+            let next = point + direction.coord();
+            let other = self.cluster_index(next).filter(|c| Some(*c) != cluster)?;
+            let is_entrance = self.clusters[other].entrances.iter().any(|e| e.contains(&next));
+            is_entrance.then(|| (next, distance(point, next)))
+        })
+    }
+
+    /// Finds the entrances on the border between `cluster` and the `neighbor` lying in
+    /// `direction` from it, which is a single corner cell for a diagonal neighbor.
+    fn build_border(&mut self, cluster: usize, neighbor: usize, direction: Direction) {
+        let rect = self.clusters[cluster].rect;
+        let edge: Vec<IVec2> = match direction {
+            Direction::North => {
+                (rect.min.x..rect.max.x).map(|x| IVec2::new(x, rect.min.y)).collect()
+            }
+            Direction::South => {
+                (rect.min.x..rect.max.x).map(|x| IVec2::new(x, rect.max.y - 1)).collect()
+            }
+            Direction::East => {
+                (rect.min.y..rect.max.y).map(|y| IVec2::new(rect.max.x - 1, y)).collect()
+            }
+            Direction::West => {
+                (rect.min.y..rect.max.y).map(|y| IVec2::new(rect.min.x, y)).collect()
+            }
+            _ => {
+                let offset = direction.coord();
+                let x = if offset.x < 0 { rect.min.x } else { rect.max.x - 1 };
+                let y = if offset.y < 0 { rect.min.y } else { rect.max.y - 1 };
+                vec![IVec2::new(x, y)]
+            }
+        };
+
+        // Steps count either way, so both sides of the border agree on where it's open.
+        let steps = self.movement.bitmap();
+        let links = |d: Direction| steps.has(d) || steps.has(d.opposite());
+        let across = |p: IVec2| -> Vec<IVec2> {
+            Direction::all()
+                .filter(|d| links(*d))
+                .map(|d| p + d.coord())
+                .filter(|q| self.cluster_index(*q) == Some(neighbor) && self.passable(*q))
+                .collect()
+        };
+        // Open cells only share an entrance if they can be walked between both ways.
+        let along = direction.right90();
+        let joined = direction.is_cardinal() && steps.has(along) && steps.has(along.opposite());
+
+        let mut entrances = Vec::new();
+        let open: Vec<bool> =
+            edge.iter().map(|p| self.passable(*p) && !across(*p).is_empty()).collect();
+        let mut i = 0;
+        while i < edge.len() {
+            if !open[i] {
+                i += 1;
+                continue;
+            }
+            let run_start = i;
+            i += 1;
+            while joined && i < edge.len() && open[i] {
+                i += 1;
+            }
+            let run = &edge[run_start..i];
+            if run.len() < MAX_SINGLE_ENTRANCE {
+                entrances.push(run[run.len() / 2]);
+            } else {
+                entrances.extend([run[0], run[run.len() - 1]]);
+            }
+        }
+
+        let mut facing: Vec<IVec2> = entrances.iter().flat_map(|p| across(*p)).collect();
+        facing.sort_unstable_by_key(|p| (p.y, p.x));
+        facing.dedup();
+        self.clusters[neighbor].entrances[direction.opposite()] = facing;
+        self.clusters[cluster].entrances[direction] = entrances;
+    }
+
+    /// Works out the cost between every pair of entrances within a cluster.
+    fn build_edges(&mut self, cluster: usize) {
+        let mut entrances: Vec<IVec2> =
+            self.clusters[cluster].entrances.iter().flatten().copied().collect();
+        entrances.sort_unstable_by_key(|p| (p.y, p.x));
+        entrances.dedup();
+
+        let edges = entrances
+            .iter()
+            .map(|from| {
+                let search = self.search(cluster, *from, false);
+                let reachable = entrances
+                    .iter()
+                    .filter(|to| *to != from)
+                    .filter_map(|to| search.cost(*to).map(|cost| (*to, cost)))
+                    .collect();
+                (*from, reachable)
+            })
+            .collect();
+        self.clusters[cluster].edges = edges;
+    }
+
+    /// Dijkstra's algorithm from `from`, without leaving the cluster.
+    ///
+    /// A `reverse` search follows every step backwards, finding the paths that lead to
+    /// `from` instead, which only differ for neighborhoods that can't step both ways.
+    fn search(&self, cluster: usize, from: IVec2, reverse: bool) -> LocalSearch {
+        let rect = self.clusters[cluster].rect;
+        let size = rect.size().as_uvec2();
+        let mut search = LocalSearch {
+            rect,
+            costs: Grid::new(size, f32::INFINITY),
+            came_from: Grid::new(size, None),
+        };
+
+        let mut open = BinaryHeap::new();
+        search.costs[from - rect.min] = 0.0;
+        open.push(Node { estimate: 0.0, cost: 0.0, point: from });
+
+        while let Some(Node { cost, point, .. }) = open.pop() {
+            if cost > search.costs[point - rect.min] {
+                continue;
+            }
+            for direction in self.movement.directions() {
+                let step = if reverse { direction.opposite() } else { direction };
+                let next = point + step.coord();
+                let local = next - rect.min;
+                let through = cost + distance(point, next);
+                if search.costs.get(local).is_some_and(|c| through < *c) && self.passable(next) {
+                    search.costs[local] = through;
+                    search.came_from[local] = Some(step);
+                    open.push(Node { estimate: through, cost: through, point: next });
+                }
+            }
+        }
+
+        search
+    }
+}
+
+impl<T, F, P> Index<P> for HierarchicalPathfinder<T, F>
+where
+    P: GridPoint,
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: P) -> &T {
+        self.grid.get_checked(index)
+    }
+}
+
+impl<T, F, P> IndexMut<P> for HierarchicalPathfinder<T, F>
+where
+    F: Fn(&T) -> bool,
+    P: GridPoint,
+{
+    #[inline]
+    fn index_mut(&mut self, index: P) -> &mut T {
+        self.get_mut(index).expect("point is out of bounds")
+    }
+}
+
+fn distance(a: IVec2, b: IVec2) -> f32 {
+    DistanceAlg::Pythagoras.distance2d(a, b)
+}
+
+/// The result of searching outward from one cell inside a cluster.
+struct LocalSearch {
+    rect: Rect,
+    costs: Grid<f32>,
+    came_from: Grid<Option<Direction>>,
+}
+
+impl LocalSearch {
+    fn cost(&self, point: IVec2) -> Option<f32> {
+        self.costs.get(point - self.rect.min).copied().filter(|c| c.is_finite())
+    }
+
+    /// The cells from the search's origin to `point`, which must have been reached.
+    fn path(&self, point: IVec2) -> Vec<IVec2> {
+        crate::astar::walk_back(&self.came_from, point - self.rect.min)
+            .into_iter()
+            .map(|p| p + self.rect.min)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::*;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn astar_cost(
+        grid: &Grid<bool>,
+        movement: Neighborhood,
+        start: IVec2,
+        goal: IVec2,
+    ) -> Option<f32> {
+        AStar::new(movement, DistanceAlg::Pythagoras)
+            .find_path(grid, start, goal, |wall| (!wall).then_some(1.0))
+            .map(|path| path.cost)
+    }
+
+    fn assert_valid(path: &Path, grid: &Grid<bool>, start: IVec2, goal: IVec2) {
+        assert_eq!(path.start(), Some(start));
+        assert_eq!(path.goal(), Some(goal));
+        assert!(path.steps.iter().all(|p| !grid[*p]));
+        assert!(path.steps.windows(2).all(|w| (w[1] - w[0]).abs().max_element() == 1));
+    }
+
+    #[test]
+    fn clusters() {
+        let hpa = HierarchicalPathfinder::new(
+            Grid::new([25, 10], false),
+            [10, 10],
+            Neighborhood::Cardinal,
+            |w: &bool| !w,
+        );
+        let clusters: Vec<_> = hpa.clusters().collect();
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[2].size(), IVec2::new(5, 10));
+        // The open border of 10 cells gets an entrance at each end.
+        assert_eq!(hpa.entrances([5, 5]).len(), 2);
+        assert_eq!(hpa.entrances([15, 5]).len(), 4);
+    }
+
+    #[test]
+    fn near_astar() {
+        let grid = Grid::new_fn([40, 30], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let mut hpa =
+            HierarchicalPathfinder::new(grid.clone(), [8, 8], Neighborhood::All, |w: &bool| !w);
+        let floors: Vec<IVec2> = grid.size().iter().filter(|p| !grid[*p]).step_by(37).collect();
+
+        let (mut hpa_total, mut astar_total) = (0.0, 0.0);
+        for start in &floors {
+            for goal in &floors {
+                let path = hpa.find_path(*start, *goal);
+                let astar = astar_cost(&grid, Neighborhood::All, *start, *goal);
+                assert_eq!(path.is_some(), astar.is_some(), "{start} -> {goal}");
+                if let (Some(path), Some(astar)) = (path, astar) {
+                    assert_valid(&path, &grid, *start, *goal);
+                    // Routes go through a few entrances per border, so they can bend a little.
+                    assert!(path.cost >= astar - 1e-3 && path.cost <= astar + 6.0);
+                    hpa_total += path.cost;
+                    astar_total += astar;
+                }
+            }
+        }
+        assert!(hpa_total < astar_total * 1.1);
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let grid = Grid::new_fn([8, 8], |p| p == IVec2::new(3, 5) || p == IVec2::new(6, 1));
+        let one_way =
+            Direction::East.bitmap() | Direction::South.bitmap() | Direction::SouthEast.bitmap();
+        for movement in [
+            Neighborhood::Cardinal,
+            Neighborhood::Ordinal,
+            Neighborhood::All,
+            Neighborhood::Custom(one_way),
+        ] {
+            let mut hpa =
+                HierarchicalPathfinder::new(grid.clone(), [4, 4], movement, |w: &bool| !w);
+            for start in grid.size().iter().filter(|p| !grid[*p]) {
+                for goal in grid.size().iter().filter(|p| !grid[*p]).step_by(3) {
+                    let path = hpa.find_path(start, goal);
+                    let astar = astar_cost(&grid, movement, start, goal);
+                    assert_eq!(path.is_some(), astar.is_some(), "{movement:?} {start} -> {goal}");
+                    if let (Some(path), Some(astar)) = (path, astar) {
+                        assert_valid(&path, &grid, start, goal);
+                        let bitmap = movement.bitmap();
+                        for step in path.steps.windows(2) {
+                            assert!(bitmap.has(Direction::from_unit_coord(step[1] - step[0])));
+                        }
+                        assert!(path.cost >= astar - 1e-3, "{movement:?} {start} -> {goal}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ordinal_open() {
+        let grid = Grid::new([8, 8], false);
+        let mut hpa =
+            HierarchicalPathfinder::new(grid, [4, 4], Neighborhood::Ordinal, |w: &bool| !w);
+        let path = hpa.find_path([1, 1], [5, 5]).unwrap();
+        assert_eq!(path.len(), 4);
+        for (start, goal) in [([0, 0], [6, 2]), ([0, 0], [6, 6]), ([2, 2], [6, 4])] {
+            assert!(hpa.find_path(start, goal).is_some(), "{start:?} -> {goal:?}");
+        }
+        assert!(hpa.find_path([0, 0], [0, 1]).is_none());
+    }
+
+    #[test]
+    fn diagonal_crossing() {
+        // The only way across the border squeezes diagonally from (3, 1) to (4, 2).
+        let grid = Grid::new_fn([8, 4], |p| (p.x == 3 && p.y != 1) || (p.x == 4 && p.y != 2));
+        let (start, goal) = (IVec2::new(0, 0), IVec2::new(7, 3));
+
+        let mut hpa =
+            HierarchicalPathfinder::new(grid.clone(), [4, 4], Neighborhood::All, |w: &bool| !w);
+        let path = hpa.find_path(start, goal).unwrap();
+        assert_valid(&path, &grid, start, goal);
+        assert!(path.steps.windows(2).any(|w| w == [IVec2::new(3, 1), IVec2::new(4, 2)]));
+
+        let mut hpa =
+            HierarchicalPathfinder::new(grid, [4, 4], Neighborhood::Cardinal, |w: &bool| !w);
+        assert!(hpa.find_path(start, goal).is_none());
+    }
+
+    #[test]
+    fn invalidation() {
+        // Two rooms joined by a single door.
+        let grid = Grid::new_fn([16, 8], |p| p.x == 8 && p.y != 4);
+        let mut hpa =
+            HierarchicalPathfinder::new(grid, [8, 8], Neighborhood::Cardinal, |w: &bool| !w);
+
+        let path = hpa.find_path([2, 2], [13, 6]).unwrap();
+        assert!(path.steps.contains(&IVec2::new(8, 4)));
+        assert!(!hpa.is_dirty());
+
+        hpa[[8, 4]] = true;
+        assert!(hpa.is_dirty());
+        assert!(hpa.find_path([2, 2], [13, 6]).is_none());
+        assert!(!hpa.is_dirty());
+
+        *hpa.get_mut([8, 1]).unwrap() = false;
+        let path = hpa.find_path([2, 2], [13, 6]).unwrap();
+        assert!(path.steps.contains(&IVec2::new(8, 1)));
+        assert_valid(&path, hpa.grid(), IVec2::new(2, 2), IVec2::new(13, 6));
+        assert_eq!(path.cost, path.len() as f32);
+    }
+
+    #[test]
+    fn same_cluster() {
+        let grid = Grid::new([20, 20], false);
+        let mut hpa = HierarchicalPathfinder::new(
+            grid.clone(),
+            [10, 10],
+            Neighborhood::Cardinal,
+            |w: &bool| !w,
+        );
+        let path = hpa.find_path([1, 1], [4, 8]).unwrap();
+        assert_valid(&path, &grid, IVec2::new(1, 1), IVec2::new(4, 8));
+        assert_eq!(path.cost, 10.0);
+
+        let path = hpa.find_path([3, 3], [3, 3]).unwrap();
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn entrance_length() {
+        // Tiles needn't be `Copy`.
+        let wall = |gap: i32| {
+            let grid = Grid::new_fn([20, 10], |p| {
+                let open = !(p.x == 9 || p.x == 10) || (2..2 + gap).contains(&p.y);
+                String::from(if open { "." } else { "#" })
+            });
+            HierarchicalPathfinder::new(grid, [10, 10], Neighborhood::Cardinal, |t: &String| {
+                t == "."
+            })
+        };
+        assert_eq!(wall(5).entrances([5, 5]).len(), 1);
+        assert_eq!(wall(6).entrances([5, 5]).len(), 2);
+        assert!(wall(6).find_path([0, 0], [19, 9]).is_some());
+    }
+}
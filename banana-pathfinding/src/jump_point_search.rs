@@ -0,0 +1,232 @@
+use crate::astar::Node;
+use crate::prelude::*;
+use banana_geometry::prelude::DistanceAlg;
+use banana_grid::prelude::*;
+use std::collections::BinaryHeap;
+
+/// Jump Point Search, as described by Daniel Harabor and Alban Grastien.
+///
+/// Finds the same cost paths as [`AStar`] with [`Neighborhood::All`] and
+/// [`DistanceAlg::Pythagoras`] over cells that all cost `1.0`, but skips over the long
+/// runs of open cells A* would otherwise expand one by one. Diagonal steps may squeeze
+/// between two walls, just like with [`AStar`].
+///
+/// [`Neighborhood::All`]: banana_direction::prelude::Neighborhood::All
+///
+/// See: [Online Graph Pruning for Pathfinding on Grid Maps](https://users.cecs.anu.edu.au/~dharabor/data/papers/harabor-grastien-aaai11.pdf)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct JumpPointSearch;
+
+impl JumpPointSearch {
+    /// Find the shortest path from `start` to `goal`, only passing through cells for
+    /// which `is_passable` returns true. Returns `None` if there is no path.
+    pub fn find_path<T, G, F>(
+        &self,
+        grid: &G,
+        start: impl GridPoint,
+        goal: impl GridPoint,
+        is_passable: F,
+    ) -> Option<Path>
+    where
+        G: GridRead<T>,
+        F: Fn(&T) -> bool,
+    {
+        let (start, goal) = (start.as_ivec2(), goal.as_ivec2());
+        let passable = |p: IVec2| grid.in_bounds(p) && grid.get(p).is_some_and(&is_passable);
+        if !grid.in_bounds(start) || !passable(goal) {
+            return None;
+        }
+
+        let jumper = Jumper { goal, passable };
+        let mut best = Grid::new(grid.size(), f32::INFINITY);
+        let mut parents: Grid<Option<IVec2>> = Grid::new(grid.size(), None);
+        let mut open = BinaryHeap::new();
+
+        best[start] = 0.0;
+        open.push(Node { estimate: distance(start, goal), cost: 0.0, point: start });
+
+        while let Some(Node { cost: so_far, point, .. }) = open.pop() {
+            if point == goal {
+                return Some(Path { steps: walk_back(&parents, goal), cost: so_far });
+            }
+            if so_far > best[point] {
+                continue;
+            }
+
+            let parent = parents[point];
+            for dir in jumper.successors(point, parent) {
+                let Some(jump_point) = jumper.jump(point, dir) else { continue };
+
+                let through = so_far + distance(point, jump_point);
+                if through < best[jump_point] {
+                    best[jump_point] = through;
+                    parents[jump_point] = Some(point);
+                    open.push(Node {
+                        estimate: through + distance(jump_point, goal),
+                        cost: through,
+                        point: jump_point,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The octile length of a straight or diagonal run between two jump points.
+fn distance(a: IVec2, b: IVec2) -> f32 {
+    DistanceAlg::Pythagoras.distance2d(a, b)
+}
+
+/// Fills in every cell between consecutive jump points, from `goal` back to the start.
+fn walk_back(parents: &Grid<Option<IVec2>>, goal: IVec2) -> Vec<IVec2> {
+    let mut steps = vec![goal];
+    let mut current = goal;
+    while let Some(parent) = parents[current] {
+        let dir = (parent - current).signum();
+        while current != parent {
+            current += dir;
+            steps.push(current);
+        }
+    }
+    steps.reverse();
+    steps
+}
+
+struct Jumper<P> {
+    goal: IVec2,
+    passable: P,
+}
+
+impl<P: Fn(IVec2) -> bool> Jumper<P> {
+    fn blocked(&self, p: IVec2) -> bool {
+        !(self.passable)(p)
+    }
+
+    /// The directions worth searching from `point`, having arrived from `parent`.
+    fn successors(&self, point: IVec2, parent: Option<IVec2>) -> Vec<IVec2> {
+        let Some(parent) = parent else {
+            return (-1..=1)
+                .flat_map(|y| (-1..=1).map(move |x| IVec2::new(x, y)))
+                .filter(|d| *d != IVec2::ZERO)
+                .collect();
+        };
+
+        let d = (point - parent).signum();
+        let mut dirs = Vec::with_capacity(5);
+        if d.x != 0 && d.y != 0 {
+            dirs.extend([d, IVec2::new(d.x, 0), IVec2::new(0, d.y)]);
+            if self.blocked(point - IVec2::new(d.x, 0)) {
+                dirs.push(IVec2::new(-d.x, d.y));
+            }
+            if self.blocked(point - IVec2::new(0, d.y)) {
+                dirs.push(IVec2::new(d.x, -d.y));
+            }
+        } else {
+            dirs.push(d);
+            let side = d.perp();
+            for side in [side, -side] {
+                if self.blocked(point + side) {
+                    dirs.push(d + side);
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Whether a cell reached by moving in `d` has a neighbor only reachable through it.
+    fn has_forced(&self, p: IVec2, d: IVec2) -> bool {
+        if d.x != 0 && d.y != 0 {
+            let (x, y) = (IVec2::new(d.x, 0), IVec2::new(0, d.y));
+            (self.blocked(p - x) && !self.blocked(p - x + y))
+                || (self.blocked(p - y) && !self.blocked(p + x - y))
+        } else {
+            let side = d.perp();
+            [side, -side].into_iter().any(|s| self.blocked(p + s) && !self.blocked(p + d + s))
+        }
+    }
+
+    /// Moves from `point` in `d` until reaching the goal or a jump point.
+    fn jump(&self, mut point: IVec2, d: IVec2) -> Option<IVec2> {
+        loop {
+            point += d;
+            if self.blocked(point) {
+                return None;
+            }
+            if point == self.goal || self.has_forced(point, d) {
+                return Some(point);
+            }
+            if d.x != 0
+                && d.y != 0
+                && (self.jump(point, IVec2::new(d.x, 0)).is_some()
+                    || self.jump(point, IVec2::new(0, d.y)).is_some())
+            {
+                return Some(point);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use banana_direction::prelude::Neighborhood;
+    use banana_geometry::prelude::DistanceAlg;
+    use banana_grid::prelude::*;
+
+    fn astar_cost(grid: &Grid<bool>, start: IVec2, goal: IVec2) -> Option<f32> {
+        AStar::new(Neighborhood::All, DistanceAlg::Pythagoras)
+            .find_path(grid, start, goal, |wall| (!wall).then_some(1.0))
+            .map(|path| path.cost)
+    }
+
+    #[test]
+    fn open_grid() {
+        let grid = Grid::new([20, 10], false);
+        let path = JumpPointSearch.find_path(&grid, [1, 1], [15, 6], |w| !w).unwrap();
+
+        assert_eq!(path.start(), Some(IVec2::new(1, 1)));
+        assert_eq!(path.goal(), Some(IVec2::new(15, 6)));
+        assert_eq!(path.len(), 14);
+        assert!(path.steps.windows(2).all(|w| (w[1] - w[0]).abs().max_element() == 1));
+        assert!((path.cost - (9.0 + 5.0 * std::f32::consts::SQRT_2)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn matches_astar() {
+        let grid = Grid::new_fn([40, 30], |p| (p.x * 7 + p.y * 13 + p.x * p.y) % 5 == 0);
+        let floors: Vec<IVec2> = grid.size().iter().filter(|p| !grid[*p]).step_by(37).collect();
+
+        for start in &floors {
+            for goal in &floors {
+                let jps = JumpPointSearch.find_path(&grid, *start, *goal, |w| !w);
+                let astar = astar_cost(&grid, *start, *goal);
+                assert_eq!(jps.is_some(), astar.is_some(), "{start} -> {goal}");
+                if let (Some(jps), Some(astar)) = (jps, astar) {
+                    assert!((jps.cost - astar).abs() < 1e-3, "{start} -> {goal}");
+                    assert!(jps.steps.iter().all(|p| !grid[*p]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_path() {
+        let grid = Grid::new_fn([5, 3], |p| p.x == 2);
+        assert!(JumpPointSearch.find_path(&grid, [0, 0], [4, 2], |w| !w).is_none());
+        assert!(JumpPointSearch.find_path(&grid, [0, 0], [2, 2], |w| !w).is_none());
+    }
+
+    #[test]
+    fn edge_modes() {
+        // Jumps stop at the edge, whatever the grid reads past it.
+        let grid = Grid::new([5, 3], false);
+        for edges in [EdgeMode::Wrap, EdgeMode::Constant(false)] {
+            let view = grid.with_edges(edges);
+            let path = JumpPointSearch.find_path(&view, [0, 0], [4, 2], |w| !w).unwrap();
+            assert_eq!(path.goal(), Some(IVec2::new(4, 2)));
+            assert!(path.steps.iter().all(|p| grid.in_bounds(*p)));
+        }
+    }
+}
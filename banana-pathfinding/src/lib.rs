@@ -0,0 +1,15 @@
+mod astar;
+mod dijkstra_map;
+mod flow_field;
+mod hierarchical;
+mod jump_point_search;
+mod path;
+
+pub mod prelude {
+    pub use crate::astar::*;
+    pub use crate::dijkstra_map::*;
+    pub use crate::flow_field::*;
+    pub use crate::hierarchical::*;
+    pub use crate::jump_point_search::*;
+    pub use crate::path::*;
+}
@@ -0,0 +1,39 @@
+use banana_grid::prelude::*;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// A path found through a grid.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Path {
+    /// Every cell along the path, from the start to the goal inclusive.
+    pub steps: Vec<IVec2>,
+    /// The summed cost of every step taken.
+    pub cost: f32,
+}
+
+impl Path {
+    /// The cell the path starts from.
+    #[inline]
+    pub fn start(&self) -> Option<IVec2> {
+        self.steps.first().copied()
+    }
+
+    /// The cell the path ends at.
+    #[inline]
+    pub fn goal(&self) -> Option<IVec2> {
+        self.steps.last().copied()
+    }
+
+    /// The number of steps taken, not counting the start.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.steps.len().saturating_sub(1)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
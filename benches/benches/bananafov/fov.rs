@@ -0,0 +1,52 @@
+use banana_lib::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const WIDTH: usize = 300;
+const HEIGHT: usize = 200;
+const RADII: [f32; 3] = [8.0, 32.0, 100.0];
+
+/// A map with roughly one wall in six cells, the same for every run.
+fn scattered_walls(origin: IVec2) -> Grid<bool> {
+    let mut rng = StdRng::seed_from_u64(1);
+    Grid::new_fn((WIDTH, HEIGHT), |p| p != origin && rng.gen_bool(1.0 / 6.0))
+}
+
+fn bench_algorithm(
+    c: &mut Criterion,
+    name: &str,
+    grid: &Grid<bool>,
+    origin: IVec2,
+    algorithm: impl FovAlgorithm,
+) {
+    let mut group = c.benchmark_group(name);
+    for radius in &RADII {
+        group.bench_with_input(BenchmarkId::from_parameter(radius), radius, |b, radius| {
+            b.iter(|| {
+                black_box(algorithm.compute(
+                    grid,
+                    origin,
+                    *radius,
+                    DistanceAlg::Pythagoras,
+                    |wall| *wall,
+                ))
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark every FOV algorithm on the same map, origin and radii.
+fn fov_bench(c: &mut Criterion) {
+    let origin = IVec2::new(WIDTH as i32 / 2, HEIGHT as i32 / 2);
+    let grid = scattered_walls(origin);
+
+    bench_algorithm(c, "SymmetricShadowcast", &grid, origin, SymmetricShadowcast);
+    bench_algorithm(c, "RecursiveShadowcast", &grid, origin, RecursiveShadowcast);
+    bench_algorithm(c, "PermissiveFov", &grid, origin, PermissiveFov);
+    bench_algorithm(c, "DiamondWalls", &grid, origin, DiamondWalls);
+    bench_algorithm(c, "RayCast", &grid, origin, RayCast);
+}
+
+criterion_group!(benches, fov_bench);
+criterion_main!(benches);
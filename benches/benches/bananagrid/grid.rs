@@ -21,7 +21,7 @@ impl GridPoint for Point {
     }
 }
 
-/// Some hard math operation to apply to the grid.
+// Some hard math operation to apply to the grid.
 // fn operation(difficulty: i32) -> impl Fn(Point) -> i32 {
 //     let f = black_box(|difficulty| {
 //         move |Point { mut x, mut y }| {
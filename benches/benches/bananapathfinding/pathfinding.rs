@@ -0,0 +1,36 @@
+use banana_lib::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const WIDTH: usize = 300;
+const HEIGHT: usize = 200;
+const WALL_CHANCES: [f64; 3] = [0.0, 0.1, 0.25];
+
+/// A map with randomly scattered walls, the same for every run. The corners are
+/// always left open.
+fn scattered_walls(chance: f64) -> Grid<bool> {
+    let mut rng = StdRng::seed_from_u64(1);
+    let corners = [IVec2::ZERO, IVec2::new(WIDTH as i32 - 1, HEIGHT as i32 - 1)];
+    Grid::new_fn((WIDTH, HEIGHT), |p| !corners.contains(&p) && rng.gen_bool(chance))
+}
+
+/// Benchmark A* against Jump Point Search, pathing corner to corner.
+fn pathfinding_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("CornerToCorner");
+    let (start, goal) = (IVec2::ZERO, IVec2::new(WIDTH as i32 - 1, HEIGHT as i32 - 1));
+    let astar = AStar::new(Neighborhood::All, DistanceAlg::Pythagoras);
+
+    for chance in &WALL_CHANCES {
+        let grid = scattered_walls(*chance);
+        group.bench_with_input(BenchmarkId::new("AStar", chance), &grid, |b, grid| {
+            b.iter(|| black_box(astar.find_path(grid, start, goal, |wall| (!wall).then_some(1.0))));
+        });
+        group.bench_with_input(BenchmarkId::new("JumpPointSearch", chance), &grid, |b, grid| {
+            b.iter(|| black_box(JumpPointSearch.find_path(grid, start, goal, |wall| !wall)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pathfinding_bench);
+criterion_main!(benches);
@@ -6,8 +6,11 @@
 /// prelude
 pub mod prelude {
     pub use banana_direction::prelude::*;
+    pub use banana_fov::prelude::*;
     pub use banana_geometry::prelude::*;
     pub use banana_grid::prelude::*;
+    pub use banana_mapgen::prelude::*;
+    pub use banana_pathfinding::prelude::*;
     pub use banana_utils::*;
 }
 
@@ -23,6 +26,18 @@ pub mod direction {
     pub use banana_direction::prelude::*;
 }
 
+pub mod fov {
+    pub use banana_fov::prelude::*;
+}
+
+pub mod mapgen {
+    pub use banana_mapgen::prelude::*;
+}
+
+pub mod pathfinding {
+    pub use banana_pathfinding::prelude::*;
+}
+
 pub mod utils {
     pub use banana_utils::*;
 }